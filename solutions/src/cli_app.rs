@@ -2,6 +2,9 @@
 pub struct Opt {
     #[structopt(long)]
     pub force_fetch: bool,
+    /// Print log messages up to this level, e.g. `debug` for Intcode instructions.
+    #[structopt(long)]
+    pub log_level: Option<log::LevelFilter>,
     #[structopt(subcommand)]
     pub cmd: Command,
 }
@@ -17,6 +20,20 @@ pub enum Command {
         day: Option<u32>,
         #[structopt(short, long)]
         single_part: Option<u32>,
+        /// Play the ASCII Intcode program of a 2019 day from the terminal, e.g. the
+        /// text adventure of day 25, instead of solving it.
+        #[structopt(long, conflicts_with = "single-part")]
+        interactive: bool,
+        /// Draw the day step by step, if it supports it.
+        #[structopt(long)]
+        visualize: bool,
+        /// Time each frame stays on screen with `--visualize`, in milliseconds.
+        #[structopt(long, default_value = "100")]
+        frame_delay: u64,
+        /// Save the pictures the day makes in this directory, e.g. the tree of 2024
+        /// day 14.
+        #[structopt(long, parse(from_os_str))]
+        export: Option<std::path::PathBuf>,
     },
     /// Run all available days.
     All {
@@ -11,7 +11,7 @@
 
 use crate::solution_runner::run_solution;
 use chrono::Datelike;
-use solution_runner::run_all_solutions;
+use solution_runner::{run_all_solutions, run_interactive};
 
 mod cli_app;
 
@@ -43,8 +43,30 @@ fn get_aoc_date() -> chrono::Date<chrono::FixedOffset> {
     aoc_timezone.date()
 }
 
+struct StderrLogger;
+
+impl log::Log for StderrLogger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        eprintln!("[{}] {}", record.level(), record.args());
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
 fn main() -> anyhow::Result<()> {
     let opt = <cli_app::Opt as structopt::StructOpt>::from_args();
+
+    if let Some(level) = opt.log_level {
+        log::set_logger(&LOGGER).map_err(|e| anyhow::anyhow!("{e}"))?;
+        log::set_max_level(level);
+    }
+
     let input_fetcher = aoc_input_fetcher::input_fetcher::InputFetcher::try_new()?;
     let date = get_aoc_date();
 
@@ -53,14 +75,28 @@ fn main() -> anyhow::Result<()> {
             year,
             day,
             single_part,
+            interactive,
+            visualize,
+            frame_delay,
+            ref export,
         } => {
-            run_solution(
-                &opt,
-                &input_fetcher,
-                year.unwrap_or_else(|| date.year()),
-                day.unwrap_or_else(|| date.day()),
-                single_part,
-            )?;
+            // Only for a single day, `all` would stop on every frame.
+            if visualize {
+                utils::viz::enable(std::time::Duration::from_millis(frame_delay));
+            }
+
+            if let Some(dir) = export {
+                utils::image::enable_export(dir.clone());
+            }
+
+            let year = year.unwrap_or_else(|| date.year());
+            let day = day.unwrap_or_else(|| date.day());
+
+            if interactive {
+                run_interactive(&opt, &input_fetcher, year, day)?;
+            } else {
+                run_solution(&opt, &input_fetcher, year, day, single_part)?;
+            }
         }
         cli_app::Command::All { year } => {
             run_all_solutions(&opt, &input_fetcher, year.unwrap_or_else(|| date.year()))?;
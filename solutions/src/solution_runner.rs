@@ -1,6 +1,6 @@
 use aoc_input_fetcher::input_fetcher::InputFetcher;
 
-use crate::{cli_app::Opt, solver::Solver};
+use crate::{cli_app::Opt, solver::Solver, y2019::intcode_ascii::AsciiComputer};
 
 fn get_start_index(year: i32) -> usize {
     let mut available_years = 2015..=2024;
@@ -62,6 +62,33 @@ pub fn run_solution(
     Ok(())
 }
 
+/// Runs the Intcode program given as input to a 2019 day, talking to it from the
+/// terminal.
+pub fn run_interactive(
+    opt: &Opt,
+    input_fetcher: &InputFetcher,
+    year: i32,
+    day: u32,
+) -> anyhow::Result<()> {
+    if year != 2019 {
+        return Err(anyhow::anyhow!(
+            "Only the Intcode programs of 2019 can be run interactively"
+        ));
+    }
+
+    let input = input_fetcher.fetch(year, day, opt.force_fetch)?;
+    let program = input
+        .trim()
+        .split(',')
+        .map(str::parse)
+        .collect::<Result<Vec<i64>, _>>()
+        .map_err(|e| anyhow::anyhow!("The input of day {day} isn't an Intcode program: {e}"))?;
+
+    AsciiComputer::new(&program).interactive()?;
+
+    Ok(())
+}
+
 pub fn run_all_solutions(opt: &Opt, input_fetcher: &InputFetcher, year: i32) -> anyhow::Result<()> {
     use colored::Colorize;
 
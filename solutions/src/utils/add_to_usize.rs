@@ -1,7 +0,0 @@
-pub fn add_to_usize(u: usize, i: i32) -> usize {
-    if i.is_negative() {
-        u - i.wrapping_abs() as u32 as usize
-    } else {
-        u + i as usize
-    }
-}
@@ -0,0 +1,294 @@
+#![allow(unused)]
+
+use std::{collections::HashMap, hash::Hash};
+
+use num::integer::lcm;
+
+/// The sequence `x0, f(x0), f(f(x0)), ...` starts with `start` states that are never
+/// seen again, then repeats every `length` states.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Smallest number of steps leading to the same state as `n` steps.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// Cycle of a state made of two independent parts, from the cycle of each part.
+    pub fn combine(self, other: Self) -> Self {
+        Self {
+            start: self.start.max(other.start),
+            length: lcm(self.length, other.length),
+        }
+    }
+}
+
+/// Floyd's tortoise and hare: only needs to compare states, and keeps two of them in
+/// memory. Never returns if the sequence doesn't cycle.
+pub fn floyd<S, F>(x0: &S, mut next: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut tortoise = next(x0);
+    let mut hare = next(&tortoise);
+
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        hare = next(&hare);
+    }
+
+    // The hare is now a multiple of the length ahead: walking both at the same speed
+    // from the start and from there, they meet at the start of the cycle.
+    let mut start = 0;
+    tortoise = x0.clone();
+
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = next(&tortoise);
+
+    while tortoise != hare {
+        hare = next(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Brent's algorithm: same as `floyd`, with fewer calls to `next`.
+pub fn brent<S, F>(x0: &S, mut next: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = next(x0);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+
+        hare = next(&hare);
+        length += 1;
+    }
+
+    let mut start = 0;
+    tortoise = x0.clone();
+    hare = x0.clone();
+
+    for _ in 0..length {
+        hare = next(&hare);
+    }
+
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Remembers every state until one comes back, which calls `next` exactly
+/// `start + length` times. `None` if the sequence ends first.
+pub fn try_find_cycle<S, F>(x0: S, mut next: F) -> Option<Cycle>
+where
+    S: Eq + Hash,
+    F: FnMut(&S) -> Option<S>,
+{
+    let mut seen = HashMap::new();
+    let mut current = x0;
+
+    for i in 0.. {
+        if let Some(&start) = seen.get(&current) {
+            return Some(Cycle {
+                start,
+                length: i - start,
+            });
+        }
+
+        let following = next(&current)?;
+        seen.insert(current, i);
+        current = following;
+    }
+
+    unreachable!()
+}
+
+pub fn find_cycle<S, F>(x0: S, mut next: F) -> Cycle
+where
+    S: Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    try_find_cycle(x0, |s| Some(next(s))).unwrap()
+}
+
+/// State after `n` steps. States are remembered until one comes back, then the
+/// remaining steps are skipped, so `n` can be huge.
+pub fn nth_state<S, F>(x0: S, mut next: F, n: usize) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut current = x0;
+
+    for i in 0..n {
+        if let Some(&start) = seen.get(&current) {
+            let cycle = Cycle {
+                start,
+                length: i - start,
+            };
+
+            return states.swap_remove(cycle.reduce(n));
+        }
+
+        seen.insert(current.clone(), i);
+        states.push(current.clone());
+        current = next(&current);
+    }
+
+    current
+}
+
+/// Cycle of a state made of `count` components that evolve independently, e.g. the
+/// axes of the moons of 2019 day 12. `component(state, i)` extracts the `i`-th one:
+/// each of them usually repeats much sooner than the whole state.
+pub fn component_cycle<S, K, F, C>(x0: S, mut next: F, count: usize, mut component: C) -> Cycle
+where
+    K: Eq + Hash,
+    F: FnMut(&S) -> S,
+    C: FnMut(&S, usize) -> K,
+{
+    let mut seen: Vec<HashMap<K, usize>> = (0..count).map(|_| HashMap::new()).collect();
+    let mut cycles: Vec<Option<Cycle>> = vec![None; count];
+    let mut current = x0;
+
+    for i in 0.. {
+        for (c, (seen, cycle)) in seen.iter_mut().zip(cycles.iter_mut()).enumerate() {
+            if cycle.is_some() {
+                continue;
+            }
+
+            let key = component(&current, c);
+            match seen.get(&key) {
+                Some(&start) => {
+                    *cycle = Some(Cycle {
+                        start,
+                        length: i - start,
+                    })
+                }
+                None => {
+                    seen.insert(key, i);
+                }
+            }
+        }
+
+        if cycles.iter().all(Option::is_some) {
+            break;
+        }
+
+        current = next(&current);
+    }
+
+    cycles
+        .into_iter()
+        .flatten()
+        .reduce(Cycle::combine)
+        .expect("No component")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, ..., 9, then 4 to 9 forever.
+    fn rho(x: &u32) -> u32 {
+        if *x == 9 {
+            4
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn test_detection() {
+        let expected = Cycle {
+            start: 4,
+            length: 6,
+        };
+
+        assert_eq!(expected, floyd(&0, rho));
+        assert_eq!(expected, brent(&0, rho));
+        assert_eq!(expected, find_cycle(0, rho));
+
+        // A pure cycle.
+        let pure = Cycle {
+            start: 0,
+            length: 6,
+        };
+        assert_eq!(pure, floyd(&4, rho));
+        assert_eq!(pure, brent(&4, rho));
+
+        assert_eq!(None, try_find_cycle(0, |&x| Some(x + 1).filter(|&x| x < 5)));
+        assert_eq!(Some(expected), try_find_cycle(0, |x| Some(rho(x))));
+    }
+
+    #[test]
+    fn test_nth_state() {
+        let mut calls = 0;
+        let mut next = |x: &u32| {
+            calls += 1;
+            rho(x)
+        };
+
+        assert_eq!(3, nth_state(0, &mut next, 3));
+        assert_eq!(7, nth_state(0, &mut next, 13));
+        assert_eq!(4, nth_state(0, &mut next, 1_000_000_000_000));
+        assert!(calls < 100);
+
+        assert_eq!(
+            7,
+            Cycle {
+                start: 4,
+                length: 6
+            }
+            .reduce(13)
+        );
+    }
+
+    #[test]
+    fn test_component_cycle() {
+        // Counts modulo 4, and goes 0, 1, 2, 3, 2, 3, ...
+        let next = |&(a, b): &(u32, u32)| ((a + 1) % 4, if b == 3 { 2 } else { b + 1 });
+        let component = |&(a, b): &(u32, u32), i: usize| if i == 0 { a } else { b };
+
+        let expected = Cycle {
+            start: 2,
+            length: 4,
+        };
+
+        assert_eq!(expected, component_cycle((0, 0), next, 2, component));
+        assert_eq!(expected, find_cycle((0, 0), next));
+    }
+}
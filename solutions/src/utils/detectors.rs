@@ -0,0 +1,239 @@
+#![allow(unused)]
+
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+
+use super::{grid::sparse::Point, search::bfs_distances_within, viz::Frame};
+
+// Simulations drawing a picture at some step are found by scoring every state: the
+// picture is where the points are the most gathered, or form the longest lines.
+
+/// Positions of the cells of the frame for which `lit` is true.
+pub fn lit_points<F, L>(frame: &F, mut lit: L) -> Vec<Point>
+where
+    F: Frame + ?Sized,
+    L: FnMut(&F::Cell) -> bool,
+{
+    let (width, height) = frame.dims();
+
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter(|&(x, y)| frame.cell(x, y).is_some_and(&mut lit))
+        .map(|(x, y)| (x as isize, y as isize))
+        .collect()
+}
+
+/// Inclusive `(min, max)` corners, if there are points.
+pub fn bounding_box(points: &[Point]) -> Option<(Point, Point)> {
+    let (min_x, max_x) = points.iter().map(|p| p.0).minmax().into_option()?;
+    let (min_y, max_y) = points.iter().map(|p| p.1).minmax().into_option()?;
+
+    Some(((min_x, min_y), (max_x, max_y)))
+}
+
+/// Number of cells of the bounding box, which shrinks until the stars of 2018 day 10
+/// spell their message.
+pub fn bounding_box_area(points: &[Point]) -> usize {
+    bounding_box(points).map_or(0, |((min_x, min_y), (max_x, max_y))| {
+        ((max_x - min_x + 1) * (max_y - min_y + 1)) as usize
+    })
+}
+
+/// Sum of the variances of both coordinates, low when the points are gathered.
+pub fn variance(points: &[Point]) -> f64 {
+    if points.is_empty() {
+        return 0.0;
+    }
+
+    let n = points.len() as f64;
+    let axis_variance = |coords: Vec<f64>| {
+        let mean = coords.iter().sum::<f64>() / n;
+        coords.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / n
+    };
+
+    axis_variance(points.iter().map(|p| p.0 as f64).collect())
+        + axis_variance(points.iter().map(|p| p.1 as f64).collect())
+}
+
+/// Shannon entropy (in bits) of how the points are spread over `block` by `block`
+/// squares, low when the picture has some structure.
+pub fn entropy(points: &[Point], block: usize) -> f64 {
+    let block = block.max(1) as isize;
+    let n = points.len() as f64;
+
+    let mut counts: HashMap<Point, usize> = HashMap::new();
+    for p in points {
+        *counts
+            .entry((p.0.div_euclid(block), p.1.div_euclid(block)))
+            .or_default() += 1;
+    }
+
+    counts
+        .values()
+        .map(|&c| {
+            let p = c as f64 / n;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Most points next to each other on a row.
+pub fn longest_run(points: &[Point]) -> usize {
+    let sorted = points
+        .iter()
+        .map(|&(x, y)| (y, x))
+        .sorted_unstable()
+        .dedup()
+        .collect_vec();
+
+    let mut longest = 0;
+    let mut run = 0;
+    for (i, &(y, x)) in sorted.iter().enumerate() {
+        run = match i.checked_sub(1).map(|j| sorted[j]) {
+            Some(previous) if previous == (y, x - 1) => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+    }
+
+    longest
+}
+
+/// Size of the biggest group of orthogonally connected points.
+pub fn largest_component(points: &[Point]) -> usize {
+    let mut remaining = points.iter().copied().collect::<HashSet<_>>();
+    let mut largest = 0;
+
+    while let Some(&start) = remaining.iter().next() {
+        let component = bfs_distances_within(
+            &start,
+            |&(x, y)| {
+                [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)]
+                    .iter()
+                    .copied()
+                    .filter(|p| remaining.contains(p))
+                    .collect_vec()
+            },
+            usize::MAX,
+        );
+
+        for p in component.keys() {
+            remaining.remove(p);
+        }
+        largest = largest.max(component.len());
+    }
+
+    largest
+}
+
+/// Index and state of the first state with the lowest score.
+pub fn min_by_score<S, I, K, F>(states: I, mut score: F) -> Option<(usize, S)>
+where
+    I: IntoIterator<Item = S>,
+    K: PartialOrd,
+    F: FnMut(&S) -> K,
+{
+    let mut best: Option<(usize, S, K)> = None;
+
+    for (i, state) in states.into_iter().enumerate() {
+        let k = score(&state);
+
+        if best.as_ref().is_none_or(|(_, _, best_k)| k < *best_k) {
+            best = Some((i, state, k));
+        }
+    }
+
+    best.map(|(i, state, _)| (i, state))
+}
+
+/// Index and state of the first state with the highest score.
+pub fn max_by_score<S, I, K, F>(states: I, mut score: F) -> Option<(usize, S)>
+where
+    I: IntoIterator<Item = S>,
+    K: PartialOrd,
+    F: FnMut(&S) -> K,
+{
+    let mut best: Option<(usize, S, K)> = None;
+
+    for (i, state) in states.into_iter().enumerate() {
+        let k = score(&state);
+
+        if best.as_ref().is_none_or(|(_, _, best_k)| k > *best_k) {
+            best = Some((i, state, k));
+        }
+    }
+
+    best.map(|(i, state, _)| (i, state))
+}
+
+/// Index and state of the last state before the score goes up, which works on endless
+/// simulations whose score decreases until the picture, then increases.
+pub fn first_local_min<S, I, K, F>(states: I, mut score: F) -> Option<(usize, S)>
+where
+    I: IntoIterator<Item = S>,
+    K: PartialOrd,
+    F: FnMut(&S) -> K,
+{
+    let mut previous: Option<(usize, S, K)> = None;
+
+    for (i, state) in states.into_iter().enumerate() {
+        let k = score(&state);
+
+        if let Some((j, previous_state, previous_k)) = previous.take() {
+            if k > previous_k {
+                return Some((j, previous_state));
+            }
+        }
+
+        previous = Some((i, state, k));
+    }
+
+    previous.map(|(i, state, _)| (i, state))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::grid::Grid;
+
+    #[test]
+    fn test_scores() {
+        let grid = Grid::from_data(
+            5,
+            "##.#.\
+             .#...\
+             .....\
+             ###.#"
+                .chars()
+                .collect(),
+        );
+        let points = lit_points(&grid, |&c| c == '#');
+
+        assert_eq!(8, points.len());
+        assert_eq!(Some(((0, 0), (4, 3))), bounding_box(&points));
+        assert_eq!(20, bounding_box_area(&points));
+        assert_eq!(3, longest_run(&points));
+        assert_eq!(3, largest_component(&points));
+        assert_eq!(0, bounding_box_area(&[]));
+
+        // Gathered points score lower than scattered ones.
+        let gathered = [(0, 0), (1, 0), (0, 1), (1, 1)];
+        let scattered = [(0, 0), (9, 0), (0, 9), (9, 9)];
+        assert!(variance(&gathered) < variance(&scattered));
+        assert_eq!(0.0, entropy(&gathered, 2));
+        assert_eq!(2.0, entropy(&scattered, 2));
+    }
+
+    #[test]
+    fn test_scan() {
+        let scores = [5, 3, 1, 2, 0, 4];
+
+        assert_eq!(Some((4, 0)), min_by_score(scores, |&s| s));
+        assert_eq!(Some((0, 5)), max_by_score(scores, |&s| s));
+        assert_eq!(Some((2, 1)), first_local_min(scores, |&s| s));
+        // Stops without going through the endless simulation.
+        assert_eq!(Some((10, 0)), first_local_min(-10.., |&s: &i32| s.abs()));
+        assert_eq!(None, min_by_score(Vec::<i32>::new(), |&s| s));
+    }
+}
@@ -0,0 +1,239 @@
+#![allow(unused)]
+
+use itertools::Itertools;
+use num::{traits::PrimInt, Integer, Signed};
+
+use super::direction::Direction;
+
+pub type Point<T> = (T, T);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PointLocation {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// Cross product of `a - o` and `b - o`: positive when `o -> a -> b` turns
+/// counter-clockwise (with `y` pointing up), zero when the points are collinear.
+pub fn cross<T>(o: Point<T>, a: Point<T>, b: Point<T>) -> T
+where
+    T: PrimInt + Signed,
+{
+    (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+}
+
+fn in_bounding_box<T>(p: Point<T>, a: Point<T>, b: Point<T>) -> bool
+where
+    T: PrimInt + Signed,
+{
+    a.0.min(b.0) <= p.0 && p.0 <= a.0.max(b.0) && a.1.min(b.1) <= p.1 && p.1 <= a.1.max(b.1)
+}
+
+pub fn is_on_segment<T>(p: Point<T>, (a, b): (Point<T>, Point<T>)) -> bool
+where
+    T: PrimInt + Signed,
+{
+    cross(a, b, p).is_zero() && in_bounding_box(p, a, b)
+}
+
+/// Whether two closed segments share at least one point, collinear overlaps and
+/// touching endpoints included.
+pub fn segments_intersect<T>((a1, a2): (Point<T>, Point<T>), (b1, b2): (Point<T>, Point<T>)) -> bool
+where
+    T: PrimInt + Signed,
+{
+    let d1 = cross(b1, b2, a1).signum();
+    let d2 = cross(b1, b2, a2).signum();
+    let d3 = cross(a1, a2, b1).signum();
+    let d4 = cross(a1, a2, b2).signum();
+
+    if d1 * d2 < T::zero() && d3 * d4 < T::zero() {
+        return true;
+    }
+
+    (d1.is_zero() && in_bounding_box(a1, b1, b2))
+        || (d2.is_zero() && in_bounding_box(a2, b1, b2))
+        || (d3.is_zero() && in_bounding_box(b1, a1, a2))
+        || (d4.is_zero() && in_bounding_box(b2, a1, a2))
+}
+
+/// A simple polygon with integer vertices. The last vertex connects back to the
+/// first one. Use `i128` when the coordinates get too big for the shoelace products
+/// to fit in an `i64`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Polygon<T> {
+    vertices: Vec<Point<T>>,
+}
+
+impl<T> Polygon<T>
+where
+    T: PrimInt + Signed + Integer,
+{
+    pub fn new(mut vertices: Vec<Point<T>>) -> Self {
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+
+        Self { vertices }
+    }
+
+    /// Follows a list of moves from the origin, e.g. the dig plan of 2023 day 18.
+    /// `Direction::Up` decreases `y`.
+    pub fn from_instructions<I>(instructions: I) -> Self
+    where
+        I: IntoIterator<Item = (Direction, T)>,
+    {
+        let unit = |d: isize| match d {
+            -1 => -T::one(),
+            1 => T::one(),
+            _ => T::zero(),
+        };
+
+        let mut pos = (T::zero(), T::zero());
+        let mut vertices = vec![pos];
+
+        for (dir, length) in instructions {
+            let (dx, dy): (isize, isize) = dir.into();
+            pos = (pos.0 + unit(dx) * length, pos.1 + unit(dy) * length);
+            vertices.push(pos);
+        }
+
+        Self::new(vertices)
+    }
+
+    pub fn vertices(&self) -> &[Point<T>] {
+        &self.vertices
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (Point<T>, Point<T>)> + '_ {
+        self.vertices.iter().copied().circular_tuple_windows()
+    }
+
+    /// Twice the signed area, which is always an integer (shoelace formula). It's
+    /// positive when the vertices go counter-clockwise with `y` pointing up.
+    pub fn twice_signed_area(&self) -> T {
+        self.edges().fold(T::zero(), |acc, ((x0, y0), (x1, y1))| {
+            acc + x0 * y1 - x1 * y0
+        })
+    }
+
+    /// Area of the polygon, rounded down when it isn't an integer.
+    pub fn area(&self) -> T {
+        self.twice_signed_area().abs() / (T::one() + T::one())
+    }
+
+    /// Sum of the lengths of the edges, measured with the Manhattan distance. This is
+    /// the exact perimeter for polygons made of horizontal and vertical edges.
+    pub fn manhattan_perimeter(&self) -> T {
+        self.edges().fold(T::zero(), |acc, (a, b)| {
+            acc + (b.0 - a.0).abs() + (b.1 - a.1).abs()
+        })
+    }
+
+    /// Number of lattice points lying on the edges.
+    pub fn boundary_points(&self) -> T {
+        self.edges()
+            .fold(T::zero(), |acc, (a, b)| acc + (b.0 - a.0).gcd(&(b.1 - a.1)))
+    }
+
+    /// Number of lattice points strictly inside, using Pick's theorem:
+    /// `A = I + B / 2 - 1`.
+    pub fn interior_points(&self) -> T {
+        let two = T::one() + T::one();
+
+        (self.twice_signed_area().abs() - self.boundary_points() + two) / two
+    }
+
+    /// Number of lattice points inside or on the boundary, e.g. the number of cubic
+    /// meters of the lagoon of 2023 day 18.
+    pub fn lattice_points(&self) -> T {
+        self.interior_points() + self.boundary_points()
+    }
+
+    pub fn locate(&self, p: Point<T>) -> PointLocation {
+        let mut inside = false;
+
+        for (a, b) in self.edges() {
+            if is_on_segment(p, (a, b)) {
+                return PointLocation::Boundary;
+            }
+
+            // Ray casting towards increasing x.
+            if (a.1 > p.1) != (b.1 > p.1) && (cross(a, b, p) > T::zero()) == (b.1 > a.1) {
+                inside = !inside;
+            }
+        }
+
+        if inside {
+            PointLocation::Inside
+        } else {
+            PointLocation::Outside
+        }
+    }
+
+    pub fn contains(&self, p: Point<T>) -> bool {
+        self.locate(p) != PointLocation::Outside
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_area_and_pick() {
+        let square: Polygon<i64> = Polygon::new(vec![(0, 0), (4, 0), (4, 4), (0, 4), (0, 0)]);
+
+        assert_eq!(4, square.vertices().len());
+        assert_eq!(32, square.twice_signed_area());
+        assert_eq!(16, square.area());
+        assert_eq!(16, square.manhattan_perimeter());
+        assert_eq!(16, square.boundary_points());
+        assert_eq!(9, square.interior_points());
+        assert_eq!(25, square.lattice_points());
+
+        let triangle: Polygon<i64> = Polygon::new(vec![(0, 0), (0, 3), (4, 0)]);
+        assert_eq!(-12, triangle.twice_signed_area());
+        assert_eq!(8, triangle.boundary_points());
+        assert_eq!(3, triangle.interior_points());
+    }
+
+    #[test]
+    fn test_from_instructions() {
+        use Direction::{Down, Left, Right, Up};
+
+        let p: Polygon<i64> =
+            Polygon::from_instructions(vec![(Right, 2), (Down, 2), (Left, 2), (Up, 2)]);
+        assert_eq!(vec![(0, 0), (2, 0), (2, 2), (0, 2)], p.vertices());
+        assert_eq!(9, p.lattice_points());
+
+        let n: i128 = 1_000_000_000_000_000;
+        let big: Polygon<i128> =
+            Polygon::from_instructions(vec![(Right, n), (Down, n), (Left, n), (Up, n)]);
+        assert_eq!((n + 1) * (n + 1), big.lattice_points());
+    }
+
+    #[test]
+    fn test_locate() {
+        // L-shaped polygon.
+        let p: Polygon<i64> = Polygon::new(vec![(0, 0), (4, 0), (4, 2), (2, 2), (2, 4), (0, 4)]);
+
+        assert_eq!(PointLocation::Inside, p.locate((1, 1)));
+        assert_eq!(PointLocation::Inside, p.locate((1, 3)));
+        assert_eq!(PointLocation::Outside, p.locate((3, 3)));
+        assert_eq!(PointLocation::Boundary, p.locate((3, 2)));
+        assert_eq!(PointLocation::Boundary, p.locate((0, 0)));
+        assert_eq!(PointLocation::Outside, p.locate((5, 0)));
+        assert!(p.contains((2, 3)));
+    }
+
+    #[test]
+    fn test_segments_intersect() {
+        assert!(segments_intersect(((0, 0), (4, 4)), ((0, 4), (4, 0))));
+        assert!(!segments_intersect(((0, 0), (1, 0)), ((0, 1), (1, 1))));
+        assert!(segments_intersect(((0, 0), (2, 0)), ((1, 0), (3, 0))));
+        assert!(!segments_intersect(((0, 0), (1, 0)), ((2, 0), (3, 0))));
+        assert!(segments_intersect(((0, 0), (2, 2)), ((2, 2), (3, 0))));
+    }
+}
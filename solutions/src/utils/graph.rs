@@ -0,0 +1,589 @@
+#![allow(unused)]
+
+use std::{
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    fmt::Debug,
+    hash::Hash,
+};
+
+use itertools::Itertools;
+use petgraph::{
+    algo::tarjan_scc,
+    graphmap::{DiGraphMap, GraphMap, NodeTrait, UnGraphMap},
+    Direction, EdgeType,
+};
+
+/// Values mapped to consecutive ids, e.g. to store per-node data in `Vec`s.
+#[derive(Clone, Debug)]
+pub struct Interner<T> {
+    ids: HashMap<T, usize>,
+    values: Vec<T>,
+}
+
+impl<T> Default for Interner<T> {
+    fn default() -> Self {
+        Self {
+            ids: HashMap::new(),
+            values: vec![],
+        }
+    }
+}
+
+impl<T: Clone + Eq + Hash> Interner<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of the value, given to it the first time it's seen.
+    pub fn intern(&mut self, value: T) -> usize {
+        if let Some(&id) = self.ids.get(&value) {
+            return id;
+        }
+
+        self.values.push(value.clone());
+        self.ids.insert(value, self.values.len() - 1);
+
+        self.values.len() - 1
+    }
+
+    pub fn id(&self, value: &T) -> Option<usize> {
+        self.ids.get(value).copied()
+    }
+
+    pub fn value(&self, id: usize) -> &T {
+        &self.values[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Edges of lines such as `a -> b, c`, `a: b c`, `a-b` or `a|b`: the first name of
+/// each line is linked to every other one.
+pub fn parse_edges(input: &str) -> Vec<(&str, &str)> {
+    input
+        .lines()
+        .flat_map(|l| {
+            let mut names = l
+                .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .filter(|n| !n.is_empty());
+            let from = names.next();
+
+            names.filter_map(move |to| Some((from?, to)))
+        })
+        .collect()
+}
+
+pub fn parse_digraph(input: &str) -> DiGraphMap<&str, ()> {
+    DiGraphMap::from_edges(parse_edges(input))
+}
+
+pub fn parse_ungraph(input: &str) -> UnGraphMap<&str, ()> {
+    UnGraphMap::from_edges(parse_edges(input))
+}
+
+/// Nodes of a cycle, the first one following the last one.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("cycle through {0:?}")]
+pub struct Cycle<N: Debug>(pub Vec<N>);
+
+/// Shortest cycle going through `start`, which must be on one.
+fn cycle_through<N: NodeTrait, E>(g: &DiGraphMap<N, E>, start: N) -> Vec<N> {
+    let mut parents = HashMap::new();
+    let mut queue = g.neighbors(start).collect::<VecDeque<_>>();
+    for &n in &queue {
+        parents.insert(n, start);
+    }
+
+    while let Some(n) = queue.pop_front() {
+        if n == start {
+            break;
+        }
+
+        for next in g.neighbors(n) {
+            if let Entry::Vacant(e) = parents.entry(next) {
+                e.insert(n);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    let mut cycle = vec![];
+    let mut n = start;
+    loop {
+        n = parents[&n];
+        cycle.push(n);
+
+        if n == start {
+            break;
+        }
+    }
+
+    cycle.reverse();
+    cycle
+}
+
+/// Nodes sorted so that every edge goes forward, or a cycle preventing it.
+pub fn toposort<N: NodeTrait + Debug, E>(g: &DiGraphMap<N, E>) -> Result<Vec<N>, Cycle<N>> {
+    petgraph::algo::toposort(g, None).map_err(|c| Cycle(cycle_through(g, c.node_id())))
+}
+
+/// `nodes` sorted by the edges between them only, e.g. the pages of an update of 2024
+/// day 5, whose rules as a whole have cycles.
+pub fn toposort_subset<N: NodeTrait + Debug, E: Clone>(
+    g: &DiGraphMap<N, E>,
+    nodes: &[N],
+) -> Result<Vec<N>, Cycle<N>> {
+    let mut subgraph = DiGraphMap::new();
+
+    for &n in nodes {
+        subgraph.add_node(n);
+
+        for (_, to, e) in g.edges(n) {
+            if nodes.contains(&to) {
+                subgraph.add_edge(n, to, e.clone());
+            }
+        }
+    }
+
+    toposort(&subgraph)
+}
+
+/// Groups of nodes that can all reach each other, in reverse topological order.
+pub fn strongly_connected_components<N: NodeTrait, E>(g: &DiGraphMap<N, E>) -> Vec<Vec<N>> {
+    tarjan_scc(g)
+}
+
+/// Nodes reachable from `start`, whatever the direction of the edges.
+pub fn connected_component<N: NodeTrait, E, Ty: EdgeType>(
+    g: &GraphMap<N, E, Ty>,
+    start: N,
+) -> HashSet<N> {
+    let mut seen = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(n) = queue.pop_front() {
+        for next in g
+            .neighbors_directed(n, Direction::Outgoing)
+            .chain(g.neighbors_directed(n, Direction::Incoming))
+        {
+            if seen.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    seen
+}
+
+/// Fewest edges to remove to split the graph in two (Stoer-Wagner), along with the
+/// nodes of one of the parts.
+pub fn min_cut<N: NodeTrait, E>(g: &UnGraphMap<N, E>) -> Option<(usize, Vec<N>)> {
+    let mut ids = Interner::new();
+    for n in g.nodes() {
+        ids.intern(n);
+    }
+
+    let n = ids.len();
+    if n < 2 {
+        return None;
+    }
+
+    // Nodes are merged along the way, `weights[a][b]` being the number of edges between
+    // the groups `a` and `b`.
+    let mut weights: Vec<HashMap<usize, usize>> = vec![HashMap::new(); n];
+    for (a, b, _) in g.all_edges() {
+        let (a, b) = (ids.intern(a), ids.intern(b));
+        if a != b {
+            *weights[a].entry(b).or_default() += 1;
+            *weights[b].entry(a).or_default() += 1;
+        }
+    }
+
+    let mut groups = (0..n).map(|i| vec![i]).collect_vec();
+    let mut active = (0..n).collect_vec();
+    let mut best: Option<(usize, Vec<usize>)> = None;
+
+    while active.len() > 1 {
+        // Adds the most tightly connected group until all of them are added, the last
+        // one being cut from the rest.
+        let mut connectivity = vec![0; n];
+        let mut added = vec![false; n];
+        let mut heap = active.iter().map(|&a| (0, a)).collect::<BinaryHeap<_>>();
+        let (mut previous, mut last) = (active[0], active[0]);
+
+        while let Some((w, a)) = heap.pop() {
+            if added[a] || w != connectivity[a] {
+                continue;
+            }
+
+            added[a] = true;
+            previous = last;
+            last = a;
+
+            for (&b, &wb) in &weights[a] {
+                if !added[b] {
+                    connectivity[b] += wb;
+                    heap.push((connectivity[b], b));
+                }
+            }
+        }
+
+        if best
+            .as_ref()
+            .is_none_or(|(cut, _)| connectivity[last] < *cut)
+        {
+            best = Some((connectivity[last], groups[last].clone()));
+        }
+
+        // Merges the last group into the previous one.
+        for (b, wb) in std::mem::take(&mut weights[last]) {
+            weights[b].remove(&last);
+
+            if b != previous {
+                *weights[previous].entry(b).or_default() += wb;
+                *weights[b].entry(previous).or_default() += wb;
+            }
+        }
+
+        let merged = std::mem::take(&mut groups[last]);
+        groups[previous].extend(merged);
+        active.retain(|&a| a != last);
+    }
+
+    best.map(|(cut, part)| (cut, part.into_iter().map(|i| *ids.value(i)).collect()))
+}
+
+/// Every group of nodes all linked to each other, that no other node could join
+/// (Bron-Kerbosch with pivot).
+pub fn maximal_cliques<N: NodeTrait, E>(g: &UnGraphMap<N, E>) -> Vec<Vec<N>> {
+    fn extend<N: NodeTrait, E>(
+        g: &UnGraphMap<N, E>,
+        clique: &mut Vec<N>,
+        mut candidates: HashSet<N>,
+        mut excluded: HashSet<N>,
+        cliques: &mut Vec<Vec<N>>,
+    ) {
+        if candidates.is_empty() {
+            if excluded.is_empty() {
+                cliques.push(clique.clone());
+            }
+
+            return;
+        }
+
+        // Nodes linked to the pivot would be found again from the pivot's clique.
+        let pivot = *candidates
+            .union(&excluded)
+            .max_by_key(|&&p| g.neighbors(p).filter(|n| candidates.contains(n)).count())
+            .unwrap();
+        let to_try = candidates
+            .iter()
+            .filter(|&&n| !g.contains_edge(pivot, n))
+            .copied()
+            .collect_vec();
+
+        for n in to_try {
+            let neighbours = g.neighbors(n).filter(|&m| m != n).collect::<HashSet<_>>();
+
+            clique.push(n);
+            extend(
+                g,
+                clique,
+                candidates.intersection(&neighbours).copied().collect(),
+                excluded.intersection(&neighbours).copied().collect(),
+                cliques,
+            );
+            clique.pop();
+
+            candidates.remove(&n);
+            excluded.insert(n);
+        }
+    }
+
+    let mut cliques = vec![];
+    extend(
+        g,
+        &mut vec![],
+        g.nodes().collect(),
+        HashSet::new(),
+        &mut cliques,
+    );
+
+    cliques
+}
+
+pub fn largest_clique<N: NodeTrait, E>(g: &UnGraphMap<N, E>) -> Vec<N> {
+    maximal_cliques(g)
+        .into_iter()
+        .max_by_key(Vec::len)
+        .unwrap_or_default()
+}
+
+/// Length and nodes of the longest path from `from` to `to` of an acyclic graph whose
+/// edges are weighted by their length. `Ok(None)` when `to` can't be reached.
+pub fn dag_longest_path<N: NodeTrait + Debug>(
+    g: &DiGraphMap<N, usize>,
+    from: N,
+    to: N,
+) -> Result<Option<(usize, Vec<N>)>, Cycle<N>> {
+    let mut best: HashMap<N, (usize, Option<N>)> = HashMap::from([(from, (0, None))]);
+
+    for n in toposort(g)? {
+        let length = match best.get(&n) {
+            Some(&(length, _)) => length,
+            None => continue,
+        };
+
+        for (_, next, &w) in g.edges(n) {
+            if best.get(&next).is_none_or(|&(l, _)| length + w > l) {
+                best.insert(next, (length + w, Some(n)));
+            }
+        }
+    }
+
+    let length = match best.get(&to) {
+        Some(&(length, _)) => length,
+        None => return Ok(None),
+    };
+
+    let mut path = vec![to];
+    while let Some(previous) = best[path.last().unwrap()].1 {
+        path.push(previous);
+    }
+    path.reverse();
+
+    Ok(Some((length, path)))
+}
+
+/// Length of the longest path from `from` to `to` that never goes through the same
+/// node twice, by trying all of them: the graph should have at most 64 nodes, like
+/// compressed mazes.
+pub fn longest_simple_path<N: NodeTrait>(
+    g: &DiGraphMap<N, usize>,
+    from: N,
+    to: N,
+) -> Option<usize> {
+    fn explore(
+        edges: &[Vec<(usize, usize)>],
+        node: usize,
+        to: usize,
+        visited: u64,
+        length: usize,
+    ) -> Option<usize> {
+        if node == to {
+            return Some(length);
+        }
+
+        edges[node]
+            .iter()
+            .filter(|&&(next, _)| visited & (1 << next) == 0)
+            .filter_map(|&(next, w)| explore(edges, next, to, visited | 1 << next, length + w))
+            .max()
+    }
+
+    let mut ids = Interner::new();
+    for n in g.nodes() {
+        ids.intern(n);
+    }
+    assert!(ids.len() <= 64, "Too many nodes to try every path");
+
+    let edges = (0..ids.len())
+        .map(|i| {
+            g.edges(*ids.value(i))
+                .map(|(_, next, &w)| (ids.id(&next).unwrap(), w))
+                .collect_vec()
+        })
+        .collect_vec();
+    let (from, to) = (ids.id(&from)?, ids.id(&to)?);
+
+    explore(&edges, from, to, 1 << from, 0)
+}
+
+/// Graph of a maze made of corridors: its nodes are `ends` and the junctions, i.e. the
+/// positions with more than two successors, linked by the length of the corridors
+/// between them (the longest one when several link the same nodes, as it's made for
+/// longest path searches). Corridors that can't be walked to their end, like dead ends
+/// or slopes of 2023 day 23 going the wrong way, aren't part of it.
+pub fn compress_corridors<N, FN, IN>(ends: &[N], mut successors: FN) -> DiGraphMap<N, usize>
+where
+    N: NodeTrait,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    let mut graph = DiGraphMap::new();
+    let mut queue = ends.iter().copied().collect::<VecDeque<_>>();
+    let mut is_node = |n: &N, successors: &[N]| successors.len() > 2 || ends.contains(n);
+
+    for &n in ends {
+        graph.add_node(n);
+    }
+
+    while let Some(node) = queue.pop_front() {
+        for first in successors(&node) {
+            let (mut previous, mut current, mut length) = (node, first, 1);
+
+            let end = loop {
+                let next = successors(&current).into_iter().collect_vec();
+
+                if is_node(&current, &next) {
+                    break Some(current);
+                }
+
+                match next.into_iter().find(|&n| n != previous) {
+                    Some(n) => {
+                        previous = current;
+                        current = n;
+                        length += 1;
+                    }
+                    None => break None,
+                }
+            };
+
+            if let Some(end) = end {
+                if !graph.contains_node(end) {
+                    queue.push_back(end);
+                }
+
+                match graph.edge_weight_mut(node, end) {
+                    Some(w) => *w = length.max(*w),
+                    None => {
+                        graph.add_edge(node, end, length);
+                    }
+                }
+            }
+        }
+    }
+
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::grid::Grid;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            vec![
+                ("a", "b"),
+                ("a", "c"),
+                ("kh", "tc"),
+                ("47", "53"),
+                ("jqt", "rhn")
+            ],
+            parse_edges("%a -> b, c\nkh-tc\n47|53\n\njqt: rhn")
+        );
+
+        let mut interner = Interner::new();
+        assert_eq!(0, interner.intern("a"));
+        assert_eq!(1, interner.intern("b"));
+        assert_eq!(0, interner.intern("a"));
+        assert_eq!((Some(1), "b"), (interner.id(&"b"), *interner.value(1)));
+    }
+
+    #[test]
+    fn test_toposort() {
+        let g = parse_digraph("a -> b, c\nb -> d\nc -> d\nd -> e");
+        let sorted = toposort(&g).unwrap();
+        let position = |n| sorted.iter().position(|&m| m == n).unwrap();
+
+        assert!(g.all_edges().all(|(a, b, _)| position(a) < position(b)));
+
+        let cyclic = parse_digraph("a -> b\nb -> c\nc -> d, a\nd -> e");
+        let Cycle(cycle) = toposort(&cyclic).unwrap_err();
+        assert_eq!(3, cycle.len());
+        assert!(cycle.iter().all(|n| ["a", "b", "c"].contains(n)));
+        assert_eq!(Ok(vec!["c", "d"]), toposort_subset(&cyclic, &["d", "c"]));
+
+        let mut components = strongly_connected_components(&cyclic)
+            .into_iter()
+            .map(|c| c.into_iter().sorted().collect_vec())
+            .collect_vec();
+        components.sort();
+        assert_eq!(vec![vec!["a", "b", "c"], vec!["d"], vec!["e"]], components);
+    }
+
+    #[test]
+    fn test_min_cut() {
+        // Two groups of 4 nodes all linked to each other, linked by 2 edges.
+        let g =
+            parse_ungraph("a-b\na-c\na-d\nb-c\nb-d\nc-d\ne-f\ne-g\ne-h\nf-g\nf-h\ng-h\na-e\nc-g");
+        let (cut, part) = min_cut(&g).unwrap();
+        let part = part.into_iter().sorted().collect_vec();
+
+        assert_eq!(2, cut);
+        assert!(part == ["a", "b", "c", "d"] || part == ["e", "f", "g", "h"]);
+        assert_eq!(8, connected_component(&g, "a").len());
+        assert_eq!(None, min_cut(&parse_ungraph("a")));
+    }
+
+    #[test]
+    fn test_cliques() {
+        let g = parse_ungraph("a-b\nb-c\nc-a\nc-d\nd-e");
+        let cliques = maximal_cliques(&g)
+            .into_iter()
+            .map(|c| c.into_iter().sorted().collect_vec())
+            .sorted()
+            .collect_vec();
+
+        assert_eq!(
+            vec![vec!["a", "b", "c"], vec!["c", "d"], vec!["d", "e"]],
+            cliques
+        );
+        assert_eq!(3, largest_clique(&g).len());
+    }
+
+    #[test]
+    fn test_corridors() {
+        let maze = Grid::from_data(
+            7,
+            "#.#####\
+             #.....#\
+             #.###.#\
+             #.....#\
+             ###.###"
+                .chars()
+                .collect(),
+        );
+        let successors = |&(x, y): &(usize, usize)| {
+            maze.ortho_neighbour_positions(x, y)
+                .filter(|&(nx, ny)| maze.get(nx, ny) == Some(&'.'))
+                .collect_vec()
+        };
+
+        // Both corridors between the junctions (1, 1) and (3, 3) are replaced by the
+        // longest one.
+        let g = compress_corridors(&[(1, 0), (3, 4)], successors);
+        assert_eq!((4, 6), (g.node_count(), g.edge_count()));
+        assert_eq!(Some(&1), g.edge_weight((1, 0), (1, 1)));
+        assert_eq!(Some(&8), g.edge_weight((1, 1), (3, 3)));
+        assert_eq!(Some(&8), g.edge_weight((3, 3), (1, 1)));
+        assert!(dag_longest_path(&g, (1, 0), (3, 4)).is_err());
+
+        let mut dag = g.clone();
+        dag.remove_edge((1, 1), (1, 0));
+        dag.remove_edge((3, 3), (1, 1));
+        dag.remove_edge((3, 4), (3, 3));
+        assert_eq!(
+            Ok(Some((10, vec![(1, 0), (1, 1), (3, 3), (3, 4)]))),
+            dag_longest_path(&dag, (1, 0), (3, 4))
+        );
+        assert_eq!(Ok(None), dag_longest_path(&dag, (3, 4), (1, 0)));
+
+        dag.add_edge((1, 1), (3, 4), 10);
+        assert_eq!(
+            Ok(Some((11, vec![(1, 0), (1, 1), (3, 4)]))),
+            dag_longest_path(&dag, (1, 0), (3, 4))
+        );
+
+        assert_eq!(Some(10), longest_simple_path(&g, (1, 0), (3, 4)));
+        assert_eq!(None, longest_simple_path(&dag, (3, 4), (1, 0)));
+    }
+}
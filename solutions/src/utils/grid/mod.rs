@@ -3,6 +3,9 @@
 pub mod cell;
 pub mod iterators;
 pub mod neighbours;
+pub mod region;
+pub mod sparse;
+pub mod transform;
 
 use std::{
     fmt::Display,
@@ -15,7 +18,7 @@ use self::{
     neighbours::Neighbours,
 };
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Grid<T> {
     data: Vec<T>,
     width: usize,
@@ -130,6 +133,28 @@ impl<T> Grid<T> {
         })
     }
 
+    /// Positions of the orthogonal neighbours of `(x, y)` that are inside the grid,
+    /// in the same order as `Neighbours`.
+    pub fn ortho_neighbour_positions(
+        &self,
+        x: usize,
+        y: usize,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        static OFFSETS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+        let (width, height) = (self.width as isize, self.height as isize);
+
+        OFFSETS.iter().filter_map(move |(dx, dy)| {
+            let (nx, ny) = (x as isize + dx, y as isize + dy);
+
+            if nx < 0 || ny < 0 || nx >= width || ny >= height {
+                None
+            } else {
+                Some((nx as usize, ny as usize))
+            }
+        })
+    }
+
     pub fn get_with_ortho_neighbours(&self, x: usize, y: usize) -> Option<(&T, Neighbours<&T>)> {
         if x >= self.width || y >= self.height {
             return None;
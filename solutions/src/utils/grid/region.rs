@@ -0,0 +1,368 @@
+use std::collections::{HashMap, HashSet};
+
+use super::Grid;
+use crate::utils::search::grid_components;
+
+type Pos = (usize, usize);
+type Vertex = (isize, isize);
+
+/// A set of orthogonally connected cells of a `Grid`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub cells: HashSet<Pos>,
+}
+
+impl Region {
+    pub fn new(cells: HashSet<Pos>) -> Self {
+        Self { cells }
+    }
+
+    fn contains_signed(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && self.cells.contains(&(x as usize, y as usize))
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.cells.contains(&pos)
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Number of cell edges between the region and the outside (holes included).
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|&(x, y)| {
+                let (x, y) = (x as isize, y as isize);
+
+                [(0, -1), (1, 0), (0, 1), (-1, 0)]
+                    .iter()
+                    .filter(|(dx, dy)| !self.contains_signed(x + dx, y + dy))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Number of straight sides of the region, which is also its number of corners.
+    pub fn sides(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|&(x, y)| {
+                let (x, y) = (x as isize, y as isize);
+                let inside = |dx: isize, dy: isize| self.contains_signed(x + dx, y + dy);
+
+                // Each cell corner is either convex (both sides are outside), or concave
+                // (both sides are inside but not the diagonal).
+                [(-1, -1), (1, -1), (1, 1), (-1, 1)]
+                    .iter()
+                    .filter(|&&(dx, dy)| {
+                        let (h, v, d) = (inside(dx, 0), inside(0, dy), inside(dx, dy));
+
+                        (!h && !v) || (h && v && !d)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Inclusive `(min, max)` corners of the cells of the region.
+    pub fn bounding_box(&self) -> Option<(Pos, Pos)> {
+        let min_x = self.cells.iter().map(|p| p.0).min()?;
+        let min_y = self.cells.iter().map(|p| p.1).min()?;
+        let max_x = self.cells.iter().map(|p| p.0).max()?;
+        let max_y = self.cells.iter().map(|p| p.1).max()?;
+
+        Some(((min_x, min_y), (max_x, max_y)))
+    }
+
+    pub fn touches_border(&self, width: usize, height: usize) -> bool {
+        self.cells
+            .iter()
+            .any(|&(x, y)| x == 0 || y == 0 || x + 1 == width || y + 1 == height)
+    }
+
+    /// Cells of the region that have at least one orthogonal neighbour outside of it.
+    pub fn boundary_cells(&self) -> HashSet<Pos> {
+        self.cells
+            .iter()
+            .copied()
+            .filter(|&(x, y)| {
+                let (x, y) = (x as isize, y as isize);
+
+                [(0, -1), (1, 0), (0, 1), (-1, 0)]
+                    .iter()
+                    .any(|(dx, dy)| !self.contains_signed(x + dx, y + dy))
+            })
+            .collect()
+    }
+
+    /// Traces the outline of the region along the cell corners: cell `(x, y)` spans
+    /// from vertex `(x, y)` to vertex `(x + 1, y + 1)`. Each loop only contains the
+    /// vertices where the outline turns, and walks with the region on its right
+    /// side, so the outer boundary is clockwise and holes are counter-clockwise
+    /// (with `y` pointing down).
+    pub fn outlines(&self) -> Vec<Vec<Vertex>> {
+        let mut edges: HashMap<Vertex, Vec<Vertex>> = HashMap::new();
+
+        for &(x, y) in &self.cells {
+            let (x, y) = (x as isize, y as isize);
+            let sides = [
+                ((0, -1), (x, y), (x + 1, y)),
+                ((1, 0), (x + 1, y), (x + 1, y + 1)),
+                ((0, 1), (x + 1, y + 1), (x, y + 1)),
+                ((-1, 0), (x, y + 1), (x, y)),
+            ];
+
+            for &((dx, dy), from, to) in &sides {
+                if !self.contains_signed(x + dx, y + dy) {
+                    edges.entry(from).or_default().push(to);
+                }
+            }
+        }
+
+        let mut outlines = vec![];
+
+        while let Some(start) = edges
+            .iter()
+            .filter(|(_, to)| !to.is_empty())
+            .map(|(from, _)| *from)
+            .min_by_key(|&(x, y)| (y, x))
+        {
+            let mut vertices = vec![];
+            let mut current = start;
+            let mut heading: Option<Vertex> = None;
+
+            loop {
+                let options = edges.get_mut(&current).unwrap();
+
+                // When two diagonal cells touch, prefer turning right so that the
+                // outline hugs the cell it came from.
+                let chosen = match heading {
+                    Some((dx, dy)) if options.len() > 1 => [(-dy, dx), (dx, dy), (dy, -dx)]
+                        .iter()
+                        .find_map(|&(tx, ty)| {
+                            options
+                                .iter()
+                                .position(|&(nx, ny)| (nx - current.0, ny - current.1) == (tx, ty))
+                        })
+                        .unwrap(),
+                    _ => 0,
+                };
+                let next = options.swap_remove(chosen);
+                let next_heading = (next.0 - current.0, next.1 - current.1);
+
+                if heading != Some(next_heading) {
+                    vertices.push(current);
+                }
+
+                heading = Some(next_heading);
+                current = next;
+
+                if current == start {
+                    break;
+                }
+            }
+
+            // The start vertex is on a straight line if the loop ends heading the
+            // same way it began.
+            if vertices.len() > 1 && vertices[0] == start {
+                let first = (vertices[1].0 - start.0, vertices[1].1 - start.1);
+                let first = (first.0.signum(), first.1.signum());
+
+                if heading == Some(first) {
+                    vertices.remove(0);
+                }
+            }
+
+            outlines.push(vertices);
+        }
+
+        outlines
+    }
+}
+
+impl<T> Grid<T> {
+    /// Connected regions of cells that have equal values, in reading order of their
+    /// first cell.
+    pub fn regions(&self) -> Vec<Region>
+    where
+        T: PartialEq,
+    {
+        self.regions_by(|a, b| a == b)
+    }
+
+    pub fn regions_by<F>(&self, connected: F) -> Vec<Region>
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        grid_components(self, connected)
+            .into_iter()
+            .map(|cells| Region::new(cells.into_iter().collect()))
+            .collect()
+    }
+
+    /// Grid of the index of the region, as returned by `regions`, of every cell.
+    pub fn label_regions(&self) -> Grid<usize>
+    where
+        T: PartialEq,
+    {
+        let mut labels = Grid::init(self.width, self.height, 0);
+
+        for (label, region) in self.regions().iter().enumerate() {
+            for &(x, y) in &region.cells {
+                labels.data[y * self.width + x] = label;
+            }
+        }
+
+        labels
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    /// Inside when crossing the loop an odd number of times.
+    EvenOdd,
+    /// Inside when the loop winds around the cell at least once.
+    NonZero,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Location {
+    Boundary,
+    Inside,
+    Outside,
+}
+
+/// Classifies every cell of a `width * height` grid relatively to a closed loop
+/// going through the centre of orthogonally adjacent cells, such as the pipe loop of
+/// 2023 day 10. The last cell of `path` connects back to the first one.
+pub fn classify_cells(path: &[Pos], width: usize, height: usize, rule: FillRule) -> Grid<Location> {
+    let mut locations = Grid::init(width, height, Location::Outside);
+    // Vertical steps crossing the horizontal ray cast just below the centre of each row.
+    let mut crossings: Vec<Vec<(usize, i32)>> = vec![vec![]; height];
+
+    for (i, &(x, y)) in path.iter().enumerate() {
+        locations.data[y * width + x] = Location::Boundary;
+
+        let (nx, ny) = path[(i + 1) % path.len()];
+        if nx == x && ny == y + 1 {
+            crossings[y].push((x, 1));
+        } else if nx == x && y == ny + 1 {
+            crossings[ny].push((x, -1));
+        }
+    }
+
+    for (y, row) in crossings.iter_mut().enumerate() {
+        row.sort_unstable();
+
+        let mut events = row.iter().peekable();
+        let mut count = 0;
+        let mut winding = 0;
+
+        for x in 0..width {
+            while let Some(&(_, direction)) = events.next_if(|&&(ex, _)| ex < x) {
+                count += 1;
+                winding += direction;
+            }
+
+            let cell = &mut locations.data[y * width + x];
+            if *cell == Location::Boundary {
+                continue;
+            }
+
+            let inside = match rule {
+                FillRule::EvenOdd => count % 2 == 1,
+                FillRule::NonZero => winding != 0,
+            };
+
+            if inside {
+                *cell = Location::Inside;
+            }
+        }
+    }
+
+    locations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid_from(input: &str) -> Grid<char> {
+        let mut grid = Grid::new();
+
+        for l in input.lines() {
+            grid.push_row(l.chars().collect());
+        }
+
+        grid
+    }
+
+    #[test]
+    fn test_regions() {
+        let grid = grid_from("AAAA\nBBCD\nBBCC\nEEEC");
+        let regions = grid.regions();
+
+        let summary: Vec<_> = regions
+            .iter()
+            .map(|r| (r.area(), r.perimeter(), r.sides()))
+            .collect();
+
+        assert_eq!(
+            vec![(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)],
+            summary
+        );
+        assert_eq!(
+            Grid::from_data(4, vec![0, 0, 0, 0, 1, 1, 2, 3, 1, 1, 2, 2, 4, 4, 4, 2]),
+            grid.label_regions()
+        );
+    }
+
+    #[test]
+    fn test_outlines_match_sides() {
+        let grid = grid_from("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO");
+        let regions = grid.regions();
+        let o = &regions[0];
+
+        assert_eq!(36, o.perimeter());
+        assert_eq!(20, o.sides());
+        assert_eq!(5, o.outlines().len());
+        assert_eq!(o.sides(), o.outlines().iter().map(Vec::len).sum::<usize>());
+        assert_eq!(vec![(0, 0), (5, 0), (5, 5), (0, 5)], o.outlines()[0]);
+        assert!(!regions[1].touches_border(5, 5));
+
+        // Two cells only touching by a corner make two separate loops.
+        let diagonal = Region::new([(0, 0), (1, 1)].iter().copied().collect());
+        assert_eq!(2, diagonal.outlines().len());
+        assert_eq!(8, diagonal.sides());
+    }
+
+    #[test]
+    fn test_classify_cells() {
+        // .....
+        // .###.
+        // .#.#.
+        // .###.
+        let path = [
+            (1, 1),
+            (2, 1),
+            (3, 1),
+            (3, 2),
+            (3, 3),
+            (2, 3),
+            (1, 3),
+            (1, 2),
+        ];
+        let locations = classify_cells(&path, 5, 4, FillRule::EvenOdd);
+
+        assert_eq!(Some(&Location::Inside), locations.get(2, 2));
+        assert_eq!(Some(&Location::Boundary), locations.get(1, 2));
+        assert_eq!(Some(&Location::Outside), locations.get(4, 2));
+        assert_eq!(
+            1,
+            locations.iter().filter(|&&l| l == Location::Inside).count()
+        );
+        assert_eq!(locations, classify_cells(&path, 5, 4, FillRule::NonZero));
+    }
+}
@@ -0,0 +1,209 @@
+use std::{collections::HashMap, fmt::Display, iter::FromIterator};
+
+use super::Grid;
+
+pub type Point = (isize, isize);
+
+/// An unbounded grid keyed by signed positions, that keeps track of the bounding
+/// box of everything that was inserted in it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    data: HashMap<Point, T>,
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            data: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    fn grow_bounds(&mut self, (x, y): Point) {
+        self.bounds = Some(match self.bounds {
+            None => ((x, y), (x, y)),
+            Some(((min_x, min_y), (max_x, max_y))) => {
+                ((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y)))
+            }
+        });
+    }
+
+    fn recompute_bounds(&mut self) {
+        self.bounds = None;
+
+        let keys = self.data.keys().copied().collect::<Vec<_>>();
+        for pos in keys {
+            self.grow_bounds(pos);
+        }
+    }
+
+    pub fn insert(&mut self, pos: Point, value: T) -> Option<T> {
+        self.grow_bounds(pos);
+        self.data.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: Point) -> Option<T> {
+        let removed = self.data.remove(&pos);
+
+        // Only a removed cell lying on the edge can shrink the bounding box.
+        if let (Some(_), Some(((min_x, min_y), (max_x, max_y)))) = (&removed, self.bounds) {
+            if pos.0 == min_x || pos.0 == max_x || pos.1 == min_y || pos.1 == max_y {
+                self.recompute_bounds();
+            }
+        }
+
+        removed
+    }
+
+    pub fn get(&self, pos: Point) -> Option<&T> {
+        self.data.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Point) -> Option<&mut T> {
+        self.data.get_mut(&pos)
+    }
+
+    pub fn get_or_insert_with<F>(&mut self, pos: Point, f: F) -> &mut T
+    where
+        F: FnOnce() -> T,
+    {
+        self.grow_bounds(pos);
+        self.data.entry(pos).or_insert_with(f)
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        self.data.contains_key(&pos)
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.bounds = None;
+    }
+
+    /// Inclusive `(min, max)` corners of the bounding box, if the grid isn't empty.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    pub fn cols(&self) -> usize {
+        self.bounds
+            .map_or(0, |((min_x, _), (max_x, _))| (max_x - min_x + 1) as usize)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.bounds
+            .map_or(0, |((_, min_y), (_, max_y))| (max_y - min_y + 1) as usize)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Point, &T)> {
+        self.data.iter()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = &Point> {
+        self.data.keys()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.data.values()
+    }
+
+    /// Dense copy of the bounding box, with `map` called on every cell (`None` for
+    /// the missing ones). The top-left cell of the result is the `min` corner of
+    /// `bounds`.
+    pub fn to_grid_with<U, F>(&self, mut map: F) -> Grid<U>
+    where
+        F: FnMut(Option<&T>) -> U,
+    {
+        let mut grid = Grid::new();
+
+        if let Some(((min_x, min_y), (max_x, max_y))) = self.bounds {
+            for y in min_y..=max_y {
+                grid.push_row((min_x..=max_x).map(|x| map(self.get((x, y)))).collect());
+            }
+        }
+
+        grid
+    }
+
+    pub fn to_grid(&self, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.to_grid_with(|v| v.unwrap_or(&fill).clone())
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(iter);
+
+        grid
+    }
+}
+
+impl<T> Extend<(Point, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point, T)>>(&mut self, iter: I) {
+        for (pos, value) in iter {
+            self.insert(pos, value);
+        }
+    }
+}
+
+impl<T> Display for SparseGrid<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let grid = self.to_grid_with(|v| v.map_or_else(String::new, ToString::to_string));
+
+        write!(f, "{grid}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds_grow_and_shrink() {
+        let mut g = SparseGrid::new();
+        assert_eq!(None, g.bounds());
+
+        g.insert((0, 0), 'a');
+        g.insert((-3, 2), 'b');
+        g.insert((4, -1), 'c');
+
+        assert_eq!(Some(((-3, -1), (4, 2))), g.bounds());
+        assert_eq!((8, 4), (g.cols(), g.rows()));
+
+        g.remove((-3, 2));
+        assert_eq!(Some(((0, -1), (4, 0))), g.bounds());
+
+        g.remove((0, 0));
+        g.remove((4, -1));
+        assert_eq!(None, g.bounds());
+    }
+
+    #[test]
+    fn test_to_grid() {
+        let g: SparseGrid<u8> = vec![((-1, -1), 1), ((1, 0), 2)].into_iter().collect();
+
+        assert_eq!(Grid::from_data(3, vec![1, 0, 0, 0, 0, 2]), g.to_grid(0));
+    }
+}
@@ -0,0 +1,432 @@
+use super::Grid;
+
+/// A borrowed, possibly rotated/flipped/cropped window over a `Grid`.
+///
+/// Every transformation on a view only composes coordinates, nothing gets copied
+/// until `to_grid` is called.
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    origin: (isize, isize),
+    x_axis: (isize, isize),
+    y_axis: (isize, isize),
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T> Clone for GridView<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for GridView<'a, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn new(grid: &'a Grid<T>) -> Self {
+        Self {
+            grid,
+            origin: (0, 0),
+            x_axis: (1, 0),
+            y_axis: (0, 1),
+            width: grid.cols(),
+            height: grid.rows(),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.height
+    }
+
+    pub fn cols(&self) -> usize {
+        self.width
+    }
+
+    /// Position in the underlying grid of the cell at `(x, y)` in this view.
+    fn source_pos(&self, x: usize, y: usize) -> (usize, usize) {
+        let (x, y) = (x as isize, y as isize);
+
+        (
+            (self.origin.0 + self.x_axis.0 * x + self.y_axis.0 * y) as usize,
+            (self.origin.1 + self.x_axis.1 * x + self.y_axis.1 * y) as usize,
+        )
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&'a T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let (sx, sy) = self.source_pos(x, y);
+        self.grid.get(sx, sy)
+    }
+
+    pub fn iter_row(&self, index: usize) -> impl Iterator<Item = &'a T> + 'a {
+        assert!(
+            index < self.height,
+            "out of bounds. Row index must be less than {:?}, but is {:?}.",
+            self.height,
+            index
+        );
+
+        let view = *self;
+        (0..self.width).map(move |x| view.get(x, index).unwrap())
+    }
+
+    pub fn iter_col(&self, index: usize) -> impl Iterator<Item = &'a T> + 'a {
+        assert!(
+            index < self.width,
+            "out of bounds. Column index must be less than {:?}, but is {:?}.",
+            self.width,
+            index
+        );
+
+        let view = *self;
+        (0..self.height).map(move |y| view.get(index, y).unwrap())
+    }
+
+    /// Iterates over all the cells, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + 'a {
+        let view = *self;
+        (0..self.height).flat_map(move |y| view.iter_row(y))
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self {
+            x_axis: self.y_axis,
+            y_axis: self.x_axis,
+            width: self.height,
+            height: self.width,
+            ..*self
+        }
+    }
+
+    pub fn flip_horizontal(&self) -> Self {
+        Self {
+            origin: self.corner(self.width.saturating_sub(1), 0),
+            x_axis: (-self.x_axis.0, -self.x_axis.1),
+            ..*self
+        }
+    }
+
+    pub fn flip_vertical(&self) -> Self {
+        Self {
+            origin: self.corner(0, self.height.saturating_sub(1)),
+            y_axis: (-self.y_axis.0, -self.y_axis.1),
+            ..*self
+        }
+    }
+
+    /// Rotates the view clockwise.
+    pub fn rotate_right(&self) -> Self {
+        Self {
+            origin: self.corner(0, self.height.saturating_sub(1)),
+            x_axis: (-self.y_axis.0, -self.y_axis.1),
+            y_axis: self.x_axis,
+            width: self.height,
+            height: self.width,
+            ..*self
+        }
+    }
+
+    /// Rotates the view counter-clockwise.
+    pub fn rotate_left(&self) -> Self {
+        Self {
+            origin: self.corner(self.width.saturating_sub(1), 0),
+            x_axis: self.y_axis,
+            y_axis: (-self.x_axis.0, -self.x_axis.1),
+            width: self.height,
+            height: self.width,
+            ..*self
+        }
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        self.flip_horizontal().flip_vertical()
+    }
+
+    /// All 8 symmetries of the dihedral group: the 4 rotations, then the 4 rotations
+    /// of the horizontally flipped view.
+    pub fn orientations(&self) -> [Self; 8] {
+        let r0 = *self;
+        let r1 = r0.rotate_right();
+        let r2 = r1.rotate_right();
+        let r3 = r2.rotate_right();
+        let f0 = r0.flip_horizontal();
+        let f1 = f0.rotate_right();
+        let f2 = f1.rotate_right();
+        let f3 = f2.rotate_right();
+
+        [r0, r1, r2, r3, f0, f1, f2, f3]
+    }
+
+    pub fn sub_view(&self, x: usize, y: usize, width: usize, height: usize) -> Self {
+        assert!(
+            x + width <= self.width && y + height <= self.height,
+            "sub-view out of bounds. {}x{} at ({}, {}) doesn't fit in {}x{}.",
+            width,
+            height,
+            x,
+            y,
+            self.width,
+            self.height
+        );
+
+        Self {
+            origin: self.corner(x, y),
+            width,
+            height,
+            ..*self
+        }
+    }
+
+    /// Drops `n` cells on every side, e.g. to remove the borders of a tile.
+    pub fn shrink(&self, n: usize) -> Self {
+        self.sub_view(
+            n,
+            n,
+            self.width.saturating_sub(2 * n),
+            self.height.saturating_sub(2 * n),
+        )
+    }
+
+    /// Same as `source_pos`, but kept signed so that empty views don't underflow.
+    fn corner(&self, x: usize, y: usize) -> (isize, isize) {
+        let (x, y) = (x as isize, y as isize);
+
+        (
+            self.origin.0 + self.x_axis.0 * x + self.y_axis.0 * y,
+            self.origin.1 + self.x_axis.1 * x + self.y_axis.1 * y,
+        )
+    }
+
+    pub fn to_grid(self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            data: self.iter().cloned().collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+/// A borrowed mosaic of same-sized views, addressed as a single large grid.
+pub struct TiledView<'a, T> {
+    tiles: Vec<Vec<GridView<'a, T>>>,
+    tile_width: usize,
+    tile_height: usize,
+}
+
+impl<'a, T> TiledView<'a, T> {
+    pub fn new(tiles: Vec<Vec<GridView<'a, T>>>) -> Self {
+        let first = tiles.first().and_then(|row| row.first());
+        let tile_width = first.map_or(0, GridView::cols);
+        let tile_height = first.map_or(0, GridView::rows);
+
+        assert!(
+            tiles.iter().all(|row| row.len() == tiles[0].len()),
+            "every row of tiles must have the same length"
+        );
+        assert!(
+            tiles
+                .iter()
+                .flatten()
+                .all(|t| t.cols() == tile_width && t.rows() == tile_height),
+            "every tile must be {}x{}",
+            tile_width,
+            tile_height
+        );
+
+        Self {
+            tiles,
+            tile_width,
+            tile_height,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.tiles.len() * self.tile_height
+    }
+
+    pub fn cols(&self) -> usize {
+        self.tiles.first().map_or(0, Vec::len) * self.tile_width
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&'a T> {
+        if x >= self.cols() || y >= self.rows() {
+            return None;
+        }
+
+        self.tiles[y / self.tile_height][x / self.tile_width]
+            .get(x % self.tile_width, y % self.tile_height)
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let (width, height) = (self.cols(), self.rows());
+        let mut data = Vec::with_capacity(width * height);
+
+        for y in 0..height {
+            for x in 0..width {
+                data.push(self.get(x, y).unwrap().clone());
+            }
+        }
+
+        Grid {
+            data,
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn view(&self) -> GridView<'_, T> {
+        GridView::new(self)
+    }
+
+    pub fn sub_view(&self, x: usize, y: usize, width: usize, height: usize) -> GridView<'_, T> {
+        self.view().sub_view(x, y, width, height)
+    }
+
+    pub fn orientation_views(&self) -> [GridView<'_, T>; 8] {
+        self.view().orientations()
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    pub fn transpose(&mut self) {
+        *self = self.view().transpose().to_grid();
+    }
+
+    pub fn flip_horizontal(&mut self) {
+        *self = self.view().flip_horizontal().to_grid();
+    }
+
+    pub fn flip_vertical(&mut self) {
+        *self = self.view().flip_vertical().to_grid();
+    }
+
+    pub fn rotate_right(&mut self) {
+        *self = self.view().rotate_right().to_grid();
+    }
+
+    pub fn rotate_left(&mut self) {
+        *self = self.view().rotate_left().to_grid();
+    }
+
+    pub fn rotate_180(&mut self) {
+        self.data.reverse();
+    }
+
+    pub fn orientations(&self) -> Vec<Self> {
+        self.orientation_views()
+            .iter()
+            .map(|view| view.to_grid())
+            .collect()
+    }
+
+    pub fn sub_grid(&self, x: usize, y: usize, width: usize, height: usize) -> Self {
+        self.sub_view(x, y, width, height).to_grid()
+    }
+
+    /// Builds a single grid out of a 2D arrangement of same-sized tiles.
+    pub fn stitch(tiles: &[Vec<Self>]) -> Self {
+        TiledView::new(
+            tiles
+                .iter()
+                .map(|row| row.iter().map(Self::view).collect())
+                .collect(),
+        )
+        .to_grid()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid_2x3() -> Grid<i32> {
+        // 1 2
+        // 3 4
+        // 5 6
+        Grid::from_data(2, vec![1, 2, 3, 4, 5, 6])
+    }
+
+    #[test]
+    fn test_rotate_rectangle() {
+        let mut g = grid_2x3();
+
+        g.rotate_right();
+        assert_eq!(Grid::from_data(3, vec![5, 3, 1, 6, 4, 2]), g);
+
+        g.rotate_right();
+        assert_eq!(Grid::from_data(2, vec![6, 5, 4, 3, 2, 1]), g);
+
+        g.rotate_left();
+        g.rotate_left();
+        assert_eq!(grid_2x3(), g);
+
+        g.rotate_180();
+        assert_eq!(Grid::from_data(2, vec![6, 5, 4, 3, 2, 1]), g);
+    }
+
+    #[test]
+    fn test_transpose_and_flips() {
+        let mut g = grid_2x3();
+        g.transpose();
+        assert_eq!(Grid::from_data(3, vec![1, 3, 5, 2, 4, 6]), g);
+
+        let mut g = grid_2x3();
+        g.flip_horizontal();
+        assert_eq!(Grid::from_data(2, vec![2, 1, 4, 3, 6, 5]), g);
+
+        let mut g = grid_2x3();
+        g.flip_vertical();
+        assert_eq!(Grid::from_data(2, vec![5, 6, 3, 4, 1, 2]), g);
+    }
+
+    #[test]
+    fn test_orientations_are_distinct() {
+        let g = Grid::from_data(3, (0..9).collect());
+        let orientations = g.orientations();
+
+        assert_eq!(8, orientations.len());
+        for (i, a) in orientations.iter().enumerate() {
+            for b in &orientations[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_views_compose() {
+        let g = Grid::from_data(4, (0..16).collect());
+
+        let inner = g.view().rotate_right().shrink(1);
+        assert_eq!(Grid::from_data(2, vec![9, 5, 10, 6]), inner.to_grid());
+
+        let sub = inner.sub_view(1, 0, 1, 2);
+        assert_eq!(vec![&5, &6], sub.iter().collect::<Vec<_>>());
+        assert_eq!(None, sub.get(1, 0));
+    }
+
+    #[test]
+    fn test_stitch() {
+        let a = Grid::from_data(2, vec![1, 2, 3, 4]);
+        let b = Grid::from_data(2, vec![5, 6, 7, 8]);
+
+        let stitched = Grid::stitch(&[vec![a.clone(), b.clone()], vec![b, a]]);
+
+        assert_eq!(
+            Grid::from_data(4, vec![1, 2, 5, 6, 3, 4, 7, 8, 5, 6, 1, 2, 7, 8, 3, 4]),
+            stitched
+        );
+    }
+}
@@ -0,0 +1,327 @@
+#![allow(unused)]
+
+use std::{
+    convert::TryFrom,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    time::Duration,
+};
+
+use super::viz::Frame;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+pub const GREEN: Rgb = [0, 153, 0];
+pub const GOLD: Rgb = [255, 204, 0];
+
+#[derive(Debug, thiserror::Error)]
+pub enum ExportError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Png(#[from] png::EncodingError),
+    #[error(transparent)]
+    Gif(#[from] gif::EncodingError),
+    #[error("unknown image format `{0}`, expected ppm or png")]
+    UnknownFormat(String),
+    #[error("{0}x{1} frame in a {2}x{3} animation")]
+    FrameSize(usize, usize, usize, usize),
+    #[error("color {0:?} isn't in the palette")]
+    UnknownColor(Rgb),
+    #[error("{0}x{1} is too large for the format")]
+    TooLarge(usize, usize),
+}
+
+static EXPORT_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Every cell of an exported frame is a square of this many pixels.
+const EXPORT_SCALE: usize = 4;
+
+/// Turns exports on, which only the `--export` option should do.
+pub fn enable_export(dir: PathBuf) {
+    EXPORT_DIR.get_or_init(|| dir);
+}
+
+/// Whether `export` saves anything. Solvers should check it before building frames
+/// only made to be exported.
+pub fn export_enabled() -> bool {
+    EXPORT_DIR.get().is_some()
+}
+
+/// Saves the frame as `<name>.png` in the directory given to `--export`, does nothing
+/// if it wasn't given.
+pub fn export<F, P>(name: &str, frame: &F, palette: P) -> Result<(), ExportError>
+where
+    F: Frame + ?Sized,
+    P: FnMut(Option<&F::Cell>) -> Rgb,
+{
+    match EXPORT_DIR.get() {
+        Some(dir) => {
+            fs::create_dir_all(dir)?;
+            Image::from_frame(frame, palette, EXPORT_SCALE).save(dir.join(format!("{name}.png")))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Palette of the frames made of lit and unlit pixels.
+pub fn pixels(lit: Option<&bool>) -> Rgb {
+    if lit == Some(&true) {
+        GREEN
+    } else {
+        BLACK
+    }
+}
+
+/// RGB picture, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// Every cell of the frame becomes a `scale` by `scale` square of the color given by
+    /// `palette`, since puzzle grids are too small to be seen one pixel per cell.
+    pub fn from_frame<F, P>(frame: &F, mut palette: P, scale: usize) -> Self
+    where
+        F: Frame + ?Sized,
+        P: FnMut(Option<&F::Cell>) -> Rgb,
+    {
+        let (cols, rows) = frame.dims();
+        let (width, height) = (cols * scale, rows * scale);
+        let mut pixels = Vec::with_capacity(width * height);
+
+        for y in 0..rows {
+            let row = (0..cols)
+                .flat_map(|x| std::iter::repeat_n(palette(frame.cell(x, y)), scale))
+                .collect::<Vec<_>>();
+
+            for _ in 0..scale {
+                pixels.extend_from_slice(&row);
+            }
+        }
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Option<Rgb> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some(self.pixels[y * self.width + x])
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flatten().copied().collect()
+    }
+
+    /// Binary PPM, which about every image viewer opens.
+    pub fn write_ppm<W: Write>(&self, mut w: W) -> Result<(), ExportError> {
+        write!(w, "P6\n{} {}\n255\n", self.width, self.height)?;
+        w.write_all(&self.bytes())?;
+
+        Ok(())
+    }
+
+    pub fn write_png<W: Write>(&self, w: W) -> Result<(), ExportError> {
+        let too_large = || ExportError::TooLarge(self.width, self.height);
+        let width = u32::try_from(self.width).map_err(|_| too_large())?;
+        let height = u32::try_from(self.height).map_err(|_| too_large())?;
+
+        let mut encoder = png::Encoder::new(w, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder.write_header()?.write_image_data(&self.bytes())?;
+
+        Ok(())
+    }
+
+    /// Writes a PPM or PNG file, depending on the extension of `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ExportError> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match extension.as_str() {
+            "ppm" => self.write_ppm(BufWriter::new(File::create(path)?)),
+            "png" => self.write_png(BufWriter::new(File::create(path)?)),
+            _ => Err(ExportError::UnknownFormat(extension)),
+        }
+    }
+}
+
+/// Animated GIF, written frame by frame so that thousands of them don't have to be
+/// kept in memory. Every frame must have the same size, and only use colors of the
+/// palette given upfront.
+pub struct Animation<W: Write> {
+    encoder: gif::Encoder<W>,
+    width: usize,
+    height: usize,
+    palette: Vec<Rgb>,
+    delay: u16,
+}
+
+impl<W: Write> Animation<W> {
+    pub fn new(
+        w: W,
+        width: usize,
+        height: usize,
+        palette: &[Rgb],
+        delay: Duration,
+    ) -> Result<Self, ExportError> {
+        assert!(
+            !palette.is_empty() && palette.len() <= 256,
+            "GIF palettes have between 1 and 256 colors"
+        );
+
+        let flat_palette = palette.iter().flatten().copied().collect::<Vec<_>>();
+        let too_large = || ExportError::TooLarge(width, height);
+        let gif_width = u16::try_from(width).map_err(|_| too_large())?;
+        let gif_height = u16::try_from(height).map_err(|_| too_large())?;
+
+        let mut encoder = gif::Encoder::new(w, gif_width, gif_height, &flat_palette)?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+
+        Ok(Self {
+            encoder,
+            width,
+            height,
+            palette: palette.to_vec(),
+            // GIF delays are in hundredths of a second.
+            delay: u16::try_from(delay.as_millis() / 10).unwrap_or(u16::MAX),
+        })
+    }
+
+    pub fn add(&mut self, image: &Image) -> Result<(), ExportError> {
+        if (image.width, image.height) != (self.width, self.height) {
+            return Err(ExportError::FrameSize(
+                image.width,
+                image.height,
+                self.width,
+                self.height,
+            ));
+        }
+
+        let indices = image
+            .pixels
+            .iter()
+            .map(|c| {
+                self.palette
+                    .iter()
+                    .position(|p| p == c)
+                    .map(|i| i as u8)
+                    .ok_or(ExportError::UnknownColor(*c))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Both fit in a `u16`, they were checked by `new`.
+        let mut frame =
+            gif::Frame::from_indexed_pixels(self.width as u16, self.height as u16, indices, None);
+        frame.delay = self.delay;
+
+        self.encoder.write_frame(&frame)?;
+
+        Ok(())
+    }
+}
+
+impl Animation<BufWriter<File>> {
+    pub fn create(
+        path: impl AsRef<Path>,
+        width: usize,
+        height: usize,
+        palette: &[Rgb],
+        delay: Duration,
+    ) -> Result<Self, ExportError> {
+        let file = BufWriter::new(File::create(path)?);
+
+        Self::new(file, width, height, palette, delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::grid::Grid;
+
+    #[test]
+    fn test_image() {
+        let grid = Grid::from_data(2, vec![true, false, false, true]);
+        let image = Image::from_frame(&grid, pixels, 2);
+
+        assert_eq!((4, 4), (image.width(), image.height()));
+        assert_eq!(Some(GREEN), image.pixel(1, 1));
+        assert_eq!(Some(BLACK), image.pixel(2, 1));
+        assert_eq!(None, image.pixel(4, 0));
+
+        let mut ppm = vec![];
+        image.write_ppm(&mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(11 + 4 * 4 * 3, ppm.len());
+
+        let mut png = vec![];
+        image.write_png(&mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    }
+
+    #[test]
+    fn test_animation() {
+        let frames = (0..3)
+            .map(|i| Grid::from_data(3, (0..3).map(|x| x == i).collect()))
+            .map(|g| Image::from_frame(&g, pixels, 1))
+            .collect::<Vec<_>>();
+
+        let mut gif = vec![];
+        {
+            let mut animation =
+                Animation::new(&mut gif, 3, 1, &[BLACK, GREEN], Duration::from_millis(50)).unwrap();
+
+            for f in &frames {
+                animation.add(f).unwrap();
+            }
+
+            let other = Image::from_frame(&Grid::from_data(1, vec![true]), pixels, 1);
+            assert!(matches!(
+                animation.add(&other),
+                Err(ExportError::FrameSize(1, 1, 3, 1))
+            ));
+        }
+
+        assert!(gif.starts_with(b"GIF89a"));
+        assert_eq!(Some(&0x3b), gif.last());
+
+        assert!(matches!(
+            Animation::new(vec![], 70_000, 1, &[BLACK], Duration::ZERO),
+            Err(ExportError::TooLarge(70_000, 1))
+        ));
+
+        let mut animation = Animation::new(vec![], 3, 1, &[BLACK], Duration::ZERO).unwrap();
+        assert!(matches!(
+            animation.add(&frames[0]),
+            Err(ExportError::UnknownColor(GREEN))
+        ));
+    }
+}
@@ -0,0 +1,241 @@
+#![allow(unused)]
+
+use std::{collections::HashMap, hash::Hash};
+
+use num::{PrimInt, Signed};
+
+/// Every function works with any signed integer, as long as it can hold the square of
+/// the moduli: use `i128` for moduli over 2^31.
+pub trait Int: PrimInt + Signed {}
+
+impl<T: PrimInt + Signed> Int for T {}
+
+/// `a mod m`, always between 0 and `m - 1`.
+pub fn modulo<T: Int>(a: T, m: T) -> T {
+    let r = a % m;
+
+    if r < T::zero() {
+        r + m
+    } else {
+        r
+    }
+}
+
+/// `(g, x, y)` such that `a * x + b * y = g`, where `g` is the (positive) gcd.
+pub fn egcd<T: Int>(a: T, b: T) -> (T, T, T) {
+    if b.is_zero() {
+        if a < T::zero() {
+            (-a, -T::one(), T::zero())
+        } else {
+            (a, T::one(), T::zero())
+        }
+    } else {
+        let (g, x, y) = egcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// `x` such that `a * x = 1 (mod m)`, if `a` and `m` are coprime.
+pub fn mod_inv<T: Int>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = egcd(modulo(a, m), m);
+
+    if g.is_one() {
+        Some(modulo(x, m))
+    } else {
+        None
+    }
+}
+
+pub fn mod_mul<T: Int>(a: T, b: T, m: T) -> T {
+    modulo(modulo(a, m) * modulo(b, m), m)
+}
+
+/// `base^exp mod m`, for `exp >= 0`.
+pub fn mod_pow<T: Int>(base: T, mut exp: T, m: T) -> T {
+    assert!(exp >= T::zero(), "Negative exponent");
+
+    let two = T::one() + T::one();
+    let mut base = modulo(base, m);
+    let mut res = modulo(T::one(), m);
+
+    while exp > T::zero() {
+        if (exp % two).is_one() {
+            res = res * base % m;
+        }
+
+        base = base * base % m;
+        exp = exp / two;
+    }
+
+    res
+}
+
+/// Chinese remainder theorem: smallest `x >= 0` such that `x = r (mod m)` for
+/// every `(r, m)`, along with the lcm of the moduli, which don't have to be coprime.
+/// `None` when the congruences contradict each other.
+pub fn crt<T: Int>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    congruences
+        .iter()
+        .try_fold((T::zero(), T::one()), |(x, lcm), &(r, m)| {
+            let (g, _, _) = egcd(lcm, m);
+            let diff = r - x;
+
+            if !(diff % g).is_zero() {
+                return None;
+            }
+
+            // x + lcm * k = r (mod m), i.e. (lcm / g) * k = diff / g (mod m / g).
+            let m_g = m / g;
+            let k = mod_mul(diff / g, mod_inv(lcm / g, m_g)?, m_g);
+            let new_lcm = lcm / g * m;
+
+            Some((modulo(x + lcm * k, new_lcm), new_lcm))
+        })
+}
+
+/// Discrete logarithm with baby-step giant-step: smallest `x` such that
+/// `base^x = target (mod m)`, with `base` and `m` coprime.
+pub fn discrete_log<T: Int + Hash>(base: T, target: T, m: T) -> Option<T> {
+    let n = T::from(m.to_f64()?.sqrt().ceil())?;
+
+    let mut baby_steps = HashMap::new();
+    let mut value = modulo(T::one(), m);
+    let mut j = T::zero();
+    while j < n {
+        baby_steps.entry(value).or_insert(j);
+        value = mod_mul(value, base, m);
+        j = j + T::one();
+    }
+
+    let giant_step = mod_pow(mod_inv(base, m)?, n, m);
+    let mut value = modulo(target, m);
+    let mut i = T::zero();
+    while i < n {
+        if let Some(&j) = baby_steps.get(&value) {
+            return Some(i * n + j);
+        }
+
+        value = mod_mul(value, giant_step, m);
+        i = i + T::one();
+    }
+
+    None
+}
+
+/// `x -> a * x + b (mod m)`. Composing them is how shuffles of 2019 day 22 can be
+/// repeated trillions of times.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Affine<T> {
+    pub a: T,
+    pub b: T,
+    pub m: T,
+}
+
+impl<T: Int> Affine<T> {
+    pub fn new(a: T, b: T, m: T) -> Self {
+        Self {
+            a: modulo(a, m),
+            b: modulo(b, m),
+            m,
+        }
+    }
+
+    pub fn identity(m: T) -> Self {
+        Self::new(T::one(), T::zero(), m)
+    }
+
+    pub fn apply(&self, x: T) -> T {
+        modulo(mod_mul(self.a, x, self.m) + self.b, self.m)
+    }
+
+    /// `self`, then `other`.
+    pub fn then(&self, other: &Self) -> Self {
+        Self::new(
+            mod_mul(other.a, self.a, self.m),
+            mod_mul(other.a, self.b, self.m) + other.b,
+            self.m,
+        )
+    }
+
+    /// `self` applied `n` times.
+    pub fn pow(&self, mut n: T) -> Self {
+        let two = T::one() + T::one();
+        let mut res = Self::identity(self.m);
+        let mut square = *self;
+
+        while n > T::zero() {
+            if (n % two).is_one() {
+                res = res.then(&square);
+            }
+
+            square = square.then(&square);
+            n = n / two;
+        }
+
+        res
+    }
+
+    /// The map undoing `self`, if `a` is invertible.
+    pub fn inverse(&self) -> Option<Self> {
+        let a = mod_inv(self.a, self.m)?;
+
+        Some(Self::new(a, -mod_mul(a, self.b, self.m), self.m))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modular_arithmetic() {
+        assert_eq!(3, modulo(-7, 10));
+        assert_eq!((2, -9, 47), egcd(240, 46));
+        assert_eq!((6, -1, 0), egcd(-6, 0));
+        assert_eq!(Some(4), mod_inv(3, 11));
+        assert_eq!(None, mod_inv(4, 10));
+        assert_eq!(445, mod_pow(4, 13, 497));
+        assert_eq!(1, mod_pow(5, 0, 7));
+
+        let m: i128 = 119_315_717_514_047;
+        assert_eq!(1, mod_pow(12345, m - 1, m));
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(Some((23, 105)), crt(&[(2, 3), (3, 5), (2, 7)]));
+        // Bus schedule of 2020 day 13.
+        assert_eq!(
+            Some(1_068_781),
+            crt(&[(0, 7), (-1, 13), (-4, 59), (-6, 31), (-7, 19)]).map(|(x, _)| x)
+        );
+        // Not coprime.
+        assert_eq!(Some((10, 12)), crt(&[(4, 6), (2, 4)]));
+        assert_eq!(None, crt(&[(1, 6), (2, 4)]));
+    }
+
+    #[test]
+    fn test_discrete_log() {
+        // Loop sizes of the example of 2020 day 25.
+        assert_eq!(Some(8), discrete_log(7_i64, 5_764_801, 20_201_227));
+        assert_eq!(Some(11), discrete_log(7_i64, 17_807_724, 20_201_227));
+        assert_eq!(None, discrete_log(2, 3, 7));
+    }
+
+    #[test]
+    fn test_affine() {
+        let f = Affine::new(3, 1, 10);
+        let g = Affine::new(-1, -1, 10);
+
+        assert_eq!(2, f.then(&g).apply(2));
+        assert_eq!(f.apply(f.apply(f.apply(4))), f.pow(3).apply(4));
+
+        let inv = f.inverse().unwrap();
+        assert_eq!(4, inv.apply(f.apply(4)));
+        assert_eq!(None, Affine::new(2, 1, 10).inverse());
+
+        let m: i128 = 119_315_717_514_047;
+        let big = Affine::new(m - 2, 12, m).pow(101_741_582_076_661);
+        assert_eq!(7, big.inverse().unwrap().apply(big.apply(7)));
+    }
+}
@@ -1,10 +1,15 @@
 #![allow(unused)]
 
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    ops::{Add, Mul},
+};
+
+use num::{rational::Ratio, Integer, Num, One, Zero};
 
 use super::direction::Direction;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Matrix<T>(pub Vec<Vec<T>>);
 
 impl<T> Display for Matrix<T>
@@ -117,6 +122,279 @@ where
     }
 }
 
+impl<T> Matrix<T> {
+    pub fn rows(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.0.first().map_or(0, Vec::len)
+    }
+
+    pub fn map<U, F>(&self, mut f: F) -> Matrix<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Matrix(
+            self.0
+                .iter()
+                .map(|row| row.iter().map(&mut f).collect())
+                .collect(),
+        )
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone,
+{
+    /// Works with any shape, unlike `transpose`.
+    pub fn transposed(&self) -> Self {
+        Self(
+            (0..self.cols())
+                .map(|j| self.0.iter().map(|row| row[j].clone()).collect())
+                .collect(),
+        )
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone + Zero + One,
+{
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self(vec![vec![T::zero(); cols]; rows])
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m.0[i][i] = T::one();
+        }
+
+        m
+    }
+
+    pub fn mul_vec(&self, v: &[T]) -> Vec<T> {
+        self.0
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(v)
+                    .fold(T::zero(), |acc, (a, b)| acc + a.clone() * b.clone())
+            })
+            .collect()
+    }
+
+    /// `self^n` for a square matrix, e.g. to jump ahead in a linear recurrence.
+    pub fn pow(&self, mut n: u64) -> Self {
+        let mut res = Self::identity(self.rows());
+        let mut square = self.clone();
+
+        while n > 0 {
+            if n % 2 == 1 {
+                res = &res * &square;
+            }
+
+            square = &square * &square;
+            n /= 2;
+        }
+
+        res
+    }
+}
+
+impl<T> Add for &Matrix<T>
+where
+    T: Clone + Add<Output = T>,
+{
+    type Output = Matrix<T>;
+
+    fn add(self, other: Self) -> Matrix<T> {
+        Matrix(
+            self.0
+                .iter()
+                .zip(&other.0)
+                .map(|(a, b)| {
+                    a.iter()
+                        .zip(b)
+                        .map(|(x, y)| x.clone() + y.clone())
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<T> Mul for &Matrix<T>
+where
+    T: Clone + Zero + One,
+{
+    type Output = Matrix<T>;
+
+    fn mul(self, other: Self) -> Matrix<T> {
+        let columns = other.transposed();
+
+        Matrix(self.0.iter().map(|row| columns.mul_vec(row)).collect())
+    }
+}
+
+/// Exact linear algebra, over a field like `Rational64` or `BigRational`.
+impl<F> Matrix<F>
+where
+    F: Clone + Num,
+{
+    /// Reduced row echelon form of the first `cols` columns, in place. Returns the
+    /// pivot columns.
+    fn reduce(&mut self, cols: usize) -> Vec<usize> {
+        let mut pivots = vec![];
+
+        for col in 0..cols {
+            let row = pivots.len();
+            let pivot = match (row..self.rows()).find(|&r| !self.0[r][col].is_zero()) {
+                Some(pivot) => pivot,
+                None => continue,
+            };
+
+            self.0.swap(pivot, row);
+
+            let p = self.0[row][col].clone();
+            for v in &mut self.0[row] {
+                *v = v.clone() / p.clone();
+            }
+
+            for r in 0..self.rows() {
+                let factor = self.0[r][col].clone();
+                if r == row || factor.is_zero() {
+                    continue;
+                }
+
+                for c in 0..self.0[r].len() {
+                    let v = self.0[row][c].clone() * factor.clone();
+                    self.0[r][c] = self.0[r][c].clone() - v;
+                }
+            }
+
+            pivots.push(col);
+        }
+
+        pivots
+    }
+
+    pub fn rank(&self) -> usize {
+        self.clone().reduce(self.cols()).len()
+    }
+
+    pub fn determinant(&self) -> F {
+        let n = self.rows();
+        let mut m = self.clone();
+        let mut det = F::one();
+
+        // Gaussian elimination, the determinant is the product of the pivots.
+        for col in 0..n {
+            let pivot = match (col..n).find(|&r| !m.0[r][col].is_zero()) {
+                Some(pivot) => pivot,
+                None => return F::zero(),
+            };
+
+            if pivot != col {
+                m.0.swap(pivot, col);
+                det = F::zero() - det;
+            }
+
+            let p = m.0[col][col].clone();
+            det = det * p.clone();
+
+            for r in col + 1..n {
+                let factor = m.0[r][col].clone() / p.clone();
+                for c in col..n {
+                    let v = m.0[col][c].clone() * factor.clone();
+                    m.0[r][c] = m.0[r][c].clone() - v;
+                }
+            }
+        }
+
+        det
+    }
+
+    /// The only `x` such that `self * x = b`, or `None` if there are none or many.
+    pub fn solve(&self, b: &[F]) -> Option<Vec<F>> {
+        let cols = self.cols();
+        let mut augmented = self.clone();
+        for (row, v) in augmented.0.iter_mut().zip(b) {
+            row.push(v.clone());
+        }
+
+        let pivots = augmented.reduce(cols);
+        if pivots.len() < cols {
+            return None;
+        }
+
+        // A row `0 = b'` with `b'` non-zero makes the system impossible.
+        if augmented.0[cols..].iter().any(|row| !row[cols].is_zero()) {
+            return None;
+        }
+
+        Some(
+            augmented.0[..cols]
+                .iter()
+                .map(|row| row[cols].clone())
+                .collect(),
+        )
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let n = self.rows();
+        let mut augmented = self.clone();
+        for (i, row) in augmented.0.iter_mut().enumerate() {
+            row.extend((0..n).map(|j| if i == j { F::one() } else { F::zero() }));
+        }
+
+        let pivots = augmented.reduce(n);
+        if pivots.len() < n {
+            return None;
+        }
+
+        Some(Self(
+            augmented
+                .0
+                .into_iter()
+                .map(|row| row[n..].to_vec())
+                .collect(),
+        ))
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone + Integer,
+{
+    pub fn to_rational(&self) -> Matrix<Ratio<T>> {
+        self.map(|v| Ratio::from_integer(v.clone()))
+    }
+
+    /// The only solution of `self * x = b`, if it's made of integers.
+    pub fn solve_integer(&self, b: &[T]) -> Option<Vec<T>> {
+        let b = b
+            .iter()
+            .cloned()
+            .map(Ratio::from_integer)
+            .collect::<Vec<_>>();
+
+        self.to_rational()
+            .solve(&b)?
+            .into_iter()
+            .map(|v| {
+                if v.is_integer() {
+                    Some(v.to_integer())
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,4 +441,57 @@ mod tests {
         assert!(a.edge_matches(&b, Direction::Up));
         assert!(b.edge_matches(&a, Direction::Down));
     }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let b = Matrix::from(vec![vec![1, 0], vec![0, 1], vec![1, 1]]);
+
+        assert_eq!(Matrix::from(vec![vec![4, 5], vec![10, 11]]), &a * &b);
+        assert_eq!(Matrix::from(vec![vec![2, 4, 6], vec![8, 10, 12]]), &a + &a);
+        assert_eq!(vec![14, 32], a.mul_vec(&[1, 2, 3]));
+        assert_eq!(
+            Matrix::from(vec![vec![1, 4], vec![2, 5], vec![3, 6]]),
+            a.transposed()
+        );
+
+        // Fibonacci numbers.
+        let fib = Matrix::from(vec![vec![1_u64, 1], vec![1, 0]]);
+        assert_eq!(12_586_269_025, fib.pow(50).0[0][1]);
+        assert_eq!(Matrix::identity(2), fib.pow(0));
+    }
+
+    #[test]
+    fn test_linear_algebra() {
+        let m = Matrix::from(vec![vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]]);
+
+        assert_eq!(Some(vec![2, 3, -1]), m.solve_integer(&[8, -11, -3]));
+        assert_eq!(Ratio::from_integer(-1), m.to_rational().determinant());
+        assert_eq!(3, m.to_rational().rank());
+
+        let inverse = m.to_rational().inverse().unwrap();
+        assert_eq!(Matrix::identity(3), &m.to_rational() * &inverse);
+
+        // A solution, but not an integer one.
+        let m = Matrix::from(vec![vec![2, 0], vec![0, 1]]);
+        assert_eq!(None, m.solve_integer(&[1, 1]));
+        assert_eq!(
+            Some(vec![Ratio::new(1, 2), Ratio::from_integer(1)]),
+            m.to_rational()
+                .solve(&[Ratio::from_integer(1), Ratio::from_integer(1)])
+        );
+
+        // No solution, then infinitely many.
+        let m = Matrix::from(vec![vec![1, 2], vec![2, 4]]);
+        assert_eq!(None, m.solve_integer(&[1, 1]));
+        assert_eq!(None, m.solve_integer(&[1, 2]));
+        assert_eq!(Ratio::from_integer(0), m.to_rational().determinant());
+        assert_eq!(None, m.to_rational().inverse());
+        assert_eq!(1, m.to_rational().rank());
+
+        // Overdetermined, but consistent.
+        let m = Matrix::from(vec![vec![1, 0], vec![0, 1], vec![1, 1]]);
+        assert_eq!(Some(vec![3, 4]), m.solve_integer(&[3, 4, 7]));
+        assert_eq!(None, m.solve_integer(&[3, 4, 8]));
+    }
 }
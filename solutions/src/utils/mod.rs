@@ -1,10 +1,22 @@
-pub mod add_to_usize;
 pub mod char_utils;
+pub mod cycle;
+pub mod detectors;
 pub mod direction;
+pub mod geometry;
 pub mod get_neighbouring_pos;
 pub mod get_width_height;
+pub mod graph;
 pub mod grid;
+pub mod image;
 pub mod iterator_index;
 pub mod manhattan;
+pub mod math;
 pub mod matrix;
+pub mod ocr;
+pub mod parse;
+pub mod ranges;
+pub mod search;
+pub mod sequence;
 pub mod to_usize_tuple;
+pub mod viz;
+pub mod vm;
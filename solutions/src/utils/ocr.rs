@@ -0,0 +1,231 @@
+#![allow(unused)]
+
+/// Letters drawn by the puzzles, 6 pixels high and usually 4 wide.
+const FONT_6: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// Letters drawn by the puzzles, 10 pixels high and 6 wide.
+const FONT_10: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+type Pixels = Vec<Vec<bool>>;
+
+/// Anything but spaces and dots is a lit pixel, so both `#` and `█` work.
+fn parse_picture(picture: &str) -> Pixels {
+    let pixels: Pixels = picture
+        .lines()
+        .map(|l| l.chars().map(|c| c != ' ' && c != '.').collect())
+        .collect();
+    let width = pixels.iter().map(Vec::len).max().unwrap_or(0);
+
+    pixels
+        .into_iter()
+        .map(|mut l| {
+            l.resize(width, false);
+            l
+        })
+        .collect()
+}
+
+/// Removes the blank borders.
+fn trim(pixels: &[Vec<bool>]) -> Pixels {
+    let lit_rows = pixels
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.iter().any(|&p| p))
+        .map(|(y, _)| y);
+    let (top, bottom) = match (lit_rows.clone().min(), lit_rows.max()) {
+        (Some(top), Some(bottom)) => (top, bottom),
+        _ => return vec![],
+    };
+
+    let width = pixels.iter().map(Vec::len).max().unwrap_or(0);
+    let lit_column = |x: usize| pixels.iter().any(|l| l.get(x) == Some(&true));
+    let left = (0..width).find(|&x| lit_column(x)).unwrap();
+    let right = (0..width).rev().find(|&x| lit_column(x)).unwrap();
+
+    pixels[top..=bottom]
+        .iter()
+        .map(|l| (left..=right).map(|x| l.get(x) == Some(&true)).collect())
+        .collect()
+}
+
+/// Splits the letters on the blank columns between them.
+fn split_glyphs(pixels: &[Vec<bool>]) -> Vec<Pixels> {
+    let width = pixels.first().map_or(0, Vec::len);
+    let mut glyphs = vec![];
+    let mut start = None;
+
+    for x in 0..=width {
+        let lit = x < width && pixels.iter().any(|l| l[x]);
+
+        match (lit, start) {
+            (true, None) => start = Some(x),
+            (false, Some(s)) => {
+                glyphs.push(pixels.iter().map(|l| l[s..x].to_vec()).collect());
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    glyphs
+}
+
+/// Letters drawn with lit pixels, or `None` if some of them aren't known.
+pub fn recognize(pixels: &[Vec<bool>]) -> Option<String> {
+    let pixels = trim(pixels);
+
+    let font: &[(char, &str)] = match pixels.len() {
+        6 => &FONT_6,
+        10 => &FONT_10,
+        _ => return None,
+    };
+    let font = font
+        .iter()
+        .map(|(c, glyph)| (*c, trim(&parse_picture(glyph))))
+        .collect::<Vec<_>>();
+
+    split_glyphs(&pixels)
+        .iter()
+        .map(|glyph| font.iter().find(|(_, g)| g == glyph).map(|(c, _)| *c))
+        .collect()
+}
+
+pub fn recognize_str(picture: &str) -> Option<String> {
+    recognize(&parse_picture(picture))
+}
+
+/// Answer of the days drawing their answer: the letters when they're recognized, the
+/// picture itself otherwise. The picture is logged either way.
+pub fn letters_or_picture(picture: String) -> String {
+    log::info!("{}", picture);
+
+    recognize_str(&picture).unwrap_or(picture)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_font_6() {
+        let picture = "
+ ##  ###   ##  #  #
+#  # #  # #  # #  #
+#  # ###  #    ####
+#### #  # #    #  #
+#  # #  # #  # #  #
+#  # ###   ##  #  #
+";
+
+        assert_eq!(Some("ABCH".to_string()), recognize_str(picture));
+        assert_eq!(
+            Some("ABCH".to_string()),
+            recognize_str(&picture.replace('#', "█"))
+        );
+        assert_eq!(None, recognize_str(&picture.replace("#  #\n", "#  #.#\n")));
+    }
+
+    #[test]
+    fn test_font_10() {
+        let picture = "\
+#....#..######
+#....#.......#
+.#..#........#
+.#..#.......#.
+..##.......#..
+..##......#...
+.#..#....#....
+.#..#...#.....
+#....#..#.....
+#....#..######";
+
+        assert_eq!(Some("XZ".to_string()), recognize_str(picture));
+    }
+
+    #[test]
+    fn test_unknown() {
+        assert_eq!(None, recognize_str(""));
+        assert_eq!(None, recognize_str("#\n#\n#"));
+        assert_eq!("#\n#\n#", letters_or_picture("#\n#\n#".to_string()));
+    }
+}
@@ -0,0 +1,328 @@
+#![allow(unused)]
+
+use std::{fmt::Display, marker::PhantomData, str::FromStr};
+
+use num::PrimInt;
+
+/// Where the input couldn't be parsed. Lines and columns start at 1.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("line {line}, column {column}: {message}")]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+/// A line of the input, which knows where it comes from to report errors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn error(&self, column: usize, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.number,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Column of `part`, which must be a slice of the line, e.g. from `split`.
+    fn column_of(&self, part: &str) -> usize {
+        let offset = (part.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+
+        if offset <= self.text.len() {
+            self.text[..offset].chars().count() + 1
+        } else {
+            1
+        }
+    }
+
+    /// Error pointing at `part`, a slice of the line.
+    pub fn error_at(&self, part: &str, message: impl Into<String>) -> ParseError {
+        self.error(self.column_of(part), message)
+    }
+
+    /// Parses `part`, a slice of the line, ignoring the spaces around it.
+    pub fn parse_part<T>(&self, part: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let part = part.trim();
+
+        part.parse()
+            .map_err(|e| self.error_at(part, format!("can't parse `{}`: {}", part, e)))
+    }
+
+    pub fn parse<T>(&self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.parse_part(self.text)
+    }
+
+    pub fn split_once(&self, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+        self.text.split_once(separator).ok_or_else(|| {
+            self.error(
+                self.text.chars().count() + 1,
+                format!("expected `{}`", separator),
+            )
+        })
+    }
+
+    /// `key: values`
+    pub fn key_values(&self) -> Result<(&'a str, &'a str), ParseError> {
+        let (key, values) = self.split_once(":")?;
+
+        Ok((key.trim(), values.trim()))
+    }
+
+    /// `a -> b, c`
+    pub fn edges(&self) -> Result<(&'a str, Vec<&'a str>), ParseError> {
+        let (from, to) = self.split_once("->")?;
+
+        Ok((from.trim(), to.split(',').map(str::trim).collect()))
+    }
+
+    /// Every field between `separator`s, parsed.
+    pub fn fields<T>(&self, separator: &str) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text
+            .split(separator)
+            .filter(|f| !f.trim().is_empty())
+            .map(|f| self.parse_part(f))
+            .collect()
+    }
+
+    pub fn ints<T: PrimInt>(&self) -> Ints<'a, T> {
+        ints(self.text)
+    }
+}
+
+/// Every line of the input, empty ones included.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text,
+    })
+}
+
+/// Groups of lines separated by blank lines.
+pub fn blocks(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut blocks = vec![vec![]];
+
+    for line in lines(input) {
+        if line.text.trim().is_empty() {
+            blocks.push(vec![]);
+        } else {
+            blocks.last_mut().unwrap().push(line);
+        }
+    }
+
+    blocks.retain(|b| !b.is_empty());
+    blocks
+}
+
+/// Parses every non-empty line, stopping at the first error.
+pub fn parse_lines<T, F>(input: &str, f: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(Line<'_>) -> Result<T, ParseError>,
+{
+    lines(input)
+        .filter(|l| !l.text.trim().is_empty())
+        .map(f)
+        .collect()
+}
+
+pub fn from_lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    parse_lines(input, |l| l.parse())
+}
+
+/// Iterator over the integers of a text, see `ints`.
+#[derive(Clone, Debug)]
+pub struct Ints<'a, T> {
+    text: &'a str,
+    position: usize,
+    _type: PhantomData<T>,
+}
+
+impl<'a, T: PrimInt> Iterator for Ints<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let bytes = self.text.as_bytes();
+
+        while self.position < bytes.len() {
+            let i = self.position;
+            let negative = bytes[i] == b'-'
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+                && (i == 0 || !bytes[i - 1].is_ascii_digit());
+
+            self.position += 1;
+
+            if bytes[i].is_ascii_digit() || negative {
+                while self.position < bytes.len() && bytes[self.position].is_ascii_digit() {
+                    self.position += 1;
+                }
+
+                let number = &self.text[i..self.position];
+                assert!(
+                    !negative || T::min_value() < T::zero(),
+                    "Negative number `{}` in `{}` for an unsigned type",
+                    number,
+                    self.text
+                );
+
+                return Some(T::from_str_radix(number, 10).unwrap_or_else(|_| {
+                    panic!(
+                        "Number `{}` in `{}` doesn't fit in its type",
+                        number, self.text
+                    )
+                }));
+            }
+        }
+
+        None
+    }
+}
+
+/// Every integer in the text, whatever separates them. A `-` is a sign unless it
+/// follows a digit, so that ranges like `1-3` give `1` and `3`. Panics on numbers
+/// that don't fit in `T`, negative ones included for unsigned types.
+pub fn ints<T: PrimInt>(s: &str) -> Ints<'_, T> {
+    Ints {
+        text: s,
+        position: 0,
+        _type: PhantomData,
+    }
+}
+
+/// The first `N` integers of the text, if there are that many.
+pub fn try_ints_n<T: PrimInt, const N: usize>(s: &str) -> Option<[T; N]> {
+    let mut values = ints(s);
+    let mut res = [T::zero(); N];
+
+    for v in &mut res {
+        *v = values.next()?;
+    }
+
+    Some(res)
+}
+
+/// The first `N` integers of the text, e.g. `let [x, y]: [i64; 2] = ints_n(line)`.
+pub fn ints_n<T: PrimInt, const N: usize>(s: &str) -> [T; N] {
+    try_ints_n(s).unwrap_or_else(|| panic!("Less than {} integers in `{}`", N, s))
+}
+
+/// The integers of every line.
+pub fn line_ints<T: PrimInt>(input: &str) -> impl Iterator<Item = Ints<'_, T>> {
+    input.lines().map(ints)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errors() {
+        let input = "1 2 3\n4 x 6\n";
+
+        assert_eq!(
+            Err(ParseError {
+                line: 2,
+                column: 3,
+                message: "can't parse `x`: invalid digit found in string".to_string()
+            }),
+            parse_lines(input, |l| l.fields::<u32>(" "))
+        );
+        assert_eq!(
+            Ok(vec![vec![1, 2, 3]]),
+            parse_lines("1 2 3\n\n", |l| l.fields::<u32>(" "))
+        );
+
+        let err = from_lines::<i32>("12\n-4\n 7a").unwrap_err();
+        assert_eq!((3, 2), (err.line, err.column));
+        assert_eq!(
+            "line 3, column 2: can't parse `7a`: invalid digit found in string",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_formats() {
+        let input = "
+seeds: 79 14
+
+broadcaster -> a, b
+%a -> b";
+        let blocks = blocks(input);
+
+        assert_eq!(2, blocks.len());
+        assert_eq!(2, blocks[0][0].number);
+
+        let (key, values) = blocks[0][0].key_values().unwrap();
+        assert_eq!(("seeds", "79 14"), (key, values));
+
+        assert_eq!(
+            ("broadcaster", vec!["a", "b"]),
+            blocks[1][0].edges().unwrap()
+        );
+        assert_eq!(("%a", vec!["b"]), blocks[1][1].edges().unwrap());
+
+        let err = blocks[0][0].edges().unwrap_err();
+        assert_eq!(
+            (2, 13, "expected `->`"),
+            (err.line, err.column, err.message.as_str())
+        );
+    }
+
+    #[test]
+    fn test_ints() {
+        let v = |s| ints(s).collect::<Vec<i64>>();
+
+        assert_eq!(vec![3, -4, 12, 5], v("p=3,-4 v=12-5"));
+        assert_eq!(vec![1, 3], v("1-3 a: abcde"));
+        assert_eq!(vec![-7, 1], v("- -7 at 1"));
+        assert_eq!(Vec::<i64>::new(), v("none"));
+
+        assert_eq!(vec![1, 3], ints::<u8>("1-3").collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "Number `300` in `300 7` doesn't fit in its type")]
+    fn test_ints_overflow() {
+        ints::<u8>("300 7").for_each(drop);
+    }
+
+    #[test]
+    #[should_panic(expected = "Negative number `-4` in `x=3, y=-4` for an unsigned type")]
+    fn test_ints_unsigned_sign() {
+        ints::<u8>("x=3, y=-4").for_each(drop);
+    }
+
+    #[test]
+    fn test_ints_n() {
+        let [x, y, z]: [i64; 3] = ints_n("<x=-1, y=0, z=2>");
+        assert_eq!((-1, 0, 2), (x, y, z));
+        assert_eq!([7_u8, 42], ints_n("7,42"));
+
+        assert_eq!(Some([1_u32, 2]), try_ints_n("1 2 3"));
+        assert_eq!(None, try_ints_n::<u32, 4>("1 2 3"));
+
+        let sums = line_ints::<i32>("1 2\n3 -4\n")
+            .map(Iterator::sum)
+            .collect::<Vec<i32>>();
+        assert_eq!(vec![3, -1], sums);
+    }
+}
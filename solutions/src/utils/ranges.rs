@@ -0,0 +1,398 @@
+#![allow(unused)]
+
+use std::iter::FromIterator;
+
+use num::PrimInt;
+
+/// Half-open interval `[start, end)`, empty when `start >= end`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    /// `[first, last]`, as most puzzles describe their ranges.
+    pub fn inclusive(first: T, last: T) -> Self {
+        Self::new(first, last + T::one())
+    }
+
+    pub fn with_len(start: T, len: T) -> Self {
+        Self::new(start, start + len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::zero()
+        } else {
+            self.end - self.start
+        }
+    }
+
+    /// Last value of the interval, which must not be empty.
+    pub fn last(&self) -> T {
+        self.end - T::one()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let i = Self::new(self.start.max(other.start), self.end.min(other.end));
+
+        if i.is_empty() {
+            None
+        } else {
+            Some(i)
+        }
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Values lower than `at`, and the others.
+    pub fn split_at(&self, at: T) -> (Option<Self>, Option<Self>) {
+        let non_empty = |i: Self| if i.is_empty() { None } else { Some(i) };
+        let at = at.max(self.start).min(self.end);
+
+        (
+            non_empty(Self::new(self.start, at)),
+            non_empty(Self::new(at, self.end)),
+        )
+    }
+
+    /// Values of `self` that aren't in `other`, before and after it.
+    pub fn difference(&self, other: &Self) -> (Option<Self>, Option<Self>) {
+        if other.is_empty() {
+            return (Some(*self).filter(|i| !i.is_empty()), None);
+        }
+
+        (self.split_at(other.start).0, self.split_at(other.end).1)
+    }
+
+    pub fn offset(&self, delta: T) -> Self {
+        Self::new(self.start + delta, self.end + delta)
+    }
+}
+
+/// Set of values stored as sorted intervals, which never overlap nor touch each other.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    fn normalize(&mut self) {
+        self.intervals.retain(|i| !i.is_empty());
+        self.intervals.sort_unstable();
+
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(self.intervals.len());
+        for i in self.intervals.drain(..) {
+            match merged.last_mut() {
+                Some(last) if i.start <= last.end => last.end = last.end.max(i.end),
+                _ => merged.push(i),
+            }
+        }
+
+        self.intervals = merged;
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of values in the set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::zero(), |acc, i| acc + i.len())
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.intervals.last().map(Interval::last)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        // The intervals are sorted, the last one starting before the value is the only
+        // one that can contain it.
+        let index = self.intervals.partition_point(|i| i.start <= value);
+        index > 0 && self.intervals[index - 1].contains(value)
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        self.intervals.push(interval);
+        self.normalize();
+    }
+
+    pub fn remove(&mut self, interval: &Interval<T>) {
+        self.intervals = self
+            .intervals
+            .iter()
+            .flat_map(|i| {
+                let (before, after) = i.difference(interval);
+                before.into_iter().chain(after)
+            })
+            .collect();
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.iter().chain(other.iter()).copied().collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let (mut a, mut b) = (0, 0);
+
+        while a < self.intervals.len() && b < other.intervals.len() {
+            let (x, y) = (&self.intervals[a], &other.intervals[b]);
+            intervals.extend(x.intersection(y));
+
+            if x.end < y.end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+
+        // Intersections of sorted disjoint intervals are already sorted and disjoint.
+        Self { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for i in other.iter() {
+            result.remove(i);
+        }
+
+        result
+    }
+
+    /// Values lower than `at`, and the others.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let (mut lower, mut upper) = (Self::new(), Self::new());
+
+        for i in self.iter() {
+            let (l, u) = i.split_at(at);
+            lower.intervals.extend(l);
+            upper.intervals.extend(u);
+        }
+
+        (lower, upper)
+    }
+
+    pub fn offset(&self, delta: T) -> Self {
+        Self {
+            intervals: self.iter().map(|i| i.offset(delta)).collect(),
+        }
+    }
+
+    /// Values inside one of the `rules` intervals are moved by its offset, the others
+    /// don't move. The rules shouldn't overlap, like the maps of 2023 day 5.
+    pub fn map_offsets(&self, rules: &[(Interval<T>, T)]) -> Self {
+        let mut unmapped = self.clone();
+        let mut mapped = vec![];
+
+        for (source, delta) in rules {
+            let moved = unmapped.intersection(&Self::from(*source));
+
+            mapped.extend(moved.iter().map(|i| i.offset(*delta)));
+            unmapped = unmapped.difference(&moved);
+        }
+
+        mapped.into_iter().chain(unmapped.intervals).collect()
+    }
+}
+
+impl<T: PrimInt> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        std::iter::once(interval).collect()
+    }
+}
+
+impl<T: PrimInt> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self {
+            intervals: iter.into_iter().collect(),
+        };
+        set.normalize();
+
+        set
+    }
+}
+
+/// Product of `N` intervals, e.g. every combination of ratings of 2023 day 19.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HyperBox<T, const N: usize>(pub [Interval<T>; N]);
+
+impl<T: PrimInt, const N: usize> HyperBox<T, N> {
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().any(Interval::is_empty)
+    }
+
+    /// Number of points inside the box.
+    pub fn volume(&self) -> T {
+        if self.is_empty() {
+            return T::zero();
+        }
+
+        self.0.iter().fold(T::one(), |acc, i| acc * i.len())
+    }
+
+    pub fn contains(&self, point: &[T; N]) -> bool {
+        self.0.iter().zip(point).all(|(i, &v)| i.contains(v))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut result = *self;
+
+        for (axis, i) in result.0.iter_mut().enumerate() {
+            *i = i.intersection(&other.0[axis])?;
+        }
+
+        Some(result)
+    }
+
+    /// Points whose coordinate on `axis` is lower than `at`, and the others.
+    pub fn split_at(&self, axis: usize, at: T) -> (Option<Self>, Option<Self>) {
+        let (lower, upper) = self.0[axis].split_at(at);
+        let with_axis = |i: Interval<T>| {
+            let mut b = *self;
+            b.0[axis] = i;
+            b
+        };
+
+        (lower.map(with_axis), upper.map(with_axis))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval() {
+        let i = Interval::inclusive(3, 7);
+
+        assert_eq!(5, i.len());
+        assert!(i.contains(7) && !i.contains(8));
+        assert_eq!(
+            Some(Interval::new(5, 8)),
+            i.intersection(&Interval::new(5, 10))
+        );
+        assert_eq!(None, i.intersection(&Interval::new(8, 10)));
+        assert_eq!(
+            (Some(Interval::new(3, 5)), Some(Interval::new(5, 8))),
+            i.split_at(5)
+        );
+        assert_eq!((None, Some(i)), i.split_at(0));
+        assert_eq!(
+            (Some(Interval::new(3, 4)), Some(Interval::new(6, 8))),
+            i.difference(&Interval::new(4, 6))
+        );
+        assert_eq!((None, Some(i)), i.difference(&Interval::new(0, 2)));
+        assert_eq!((None, None), i.difference(&Interval::new(0, 10)));
+        assert_eq!(Interval::new(0, 5), i.offset(-3));
+    }
+
+    #[test]
+    fn test_interval_set() {
+        let a: IntervalSet<i32> = vec![
+            Interval::new(10, 15),
+            Interval::new(0, 5),
+            Interval::new(5, 7),
+            Interval::new(12, 20),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(&[Interval::new(0, 7), Interval::new(10, 20)], a.intervals());
+        assert_eq!(17, a.len());
+        assert_eq!((Some(0), Some(19)), (a.min(), a.max()));
+        assert!(a.contains(6) && !a.contains(7) && a.contains(10));
+
+        let b = IntervalSet::from(Interval::new(5, 12));
+        assert_eq!(
+            &[Interval::new(5, 7), Interval::new(10, 12)],
+            a.intersection(&b).intervals()
+        );
+        assert_eq!(
+            &[Interval::new(0, 5), Interval::new(12, 20)],
+            a.difference(&b).intervals()
+        );
+        assert_eq!(&[Interval::new(0, 20)], a.union(&b).intervals());
+
+        let (lower, upper) = a.split_at(3);
+        assert_eq!(&[Interval::new(0, 3)], lower.intervals());
+        assert_eq!(
+            &[Interval::new(3, 7), Interval::new(10, 20)],
+            upper.intervals()
+        );
+    }
+
+    #[test]
+    fn test_map_offsets() {
+        // Seeds 79 to 92 and 55 to 67, through the seed-to-soil map of 2023 day 5.
+        let seeds: IntervalSet<i64> = vec![Interval::with_len(79, 14), Interval::with_len(55, 13)]
+            .into_iter()
+            .collect();
+        let rules = [
+            (Interval::with_len(98, 2), 50 - 98),
+            (Interval::with_len(50, 48), 52 - 50),
+        ];
+
+        assert_eq!(
+            &[Interval::new(57, 70), Interval::new(81, 95)],
+            seeds.map_offsets(&rules).intervals()
+        );
+        assert_eq!(
+            &[Interval::new(50, 52), Interval::new(100, 101)],
+            IntervalSet::from(Interval::new(98, 101))
+                .map_offsets(&rules)
+                .intervals()
+        );
+    }
+
+    #[test]
+    fn test_hyper_box() {
+        let b = HyperBox([Interval::inclusive(1, 4000); 4]);
+        assert_eq!(256_000_000_000_000_u64, b.volume());
+
+        let (lower, upper) = b.split_at(2, 2006);
+        let (lower, upper) = (lower.unwrap(), upper.unwrap());
+        assert_eq!(Interval::new(1, 2006), lower.0[2]);
+        assert_eq!(b.volume(), lower.volume() + upper.volume());
+        assert!(lower.contains(&[4000, 1, 2005, 1]));
+        assert!(!lower.contains(&[4000, 1, 2006, 1]));
+        assert_eq!(None, lower.intersection(&upper));
+    }
+}
@@ -0,0 +1,733 @@
+#![allow(unused)]
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+use num::Zero;
+
+use super::grid::Grid;
+
+fn reconstruct_path<N>(parents: &HashMap<N, N>, end: N) -> Vec<N>
+where
+    N: Eq + Hash + Clone,
+{
+    let mut path = vec![end];
+
+    while let Some(parent) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+
+    path.reverse();
+    path
+}
+
+/// Shortest path (in number of steps) from `start` to the first node matching `success`.
+pub fn bfs<N, FN, IN, FS>(start: &N, mut successors: FN, mut success: FS) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+{
+    let mut parents: HashMap<N, N> = HashMap::new();
+    let mut visited: HashSet<N> = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    visited.insert(start.clone());
+    queue.push_back(start.clone());
+
+    while let Some(node) = queue.pop_front() {
+        if success(&node) {
+            return Some(reconstruct_path(&parents, node));
+        }
+
+        for next in successors(&node) {
+            if visited.insert(next.clone()) {
+                parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Depth-first path from `start` to the first node matching `success`. The path is
+/// not necessarily the shortest one.
+pub fn dfs<N, FN, IN, FS>(start: &N, mut successors: FN, mut success: FS) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+{
+    let mut parents: HashMap<N, N> = HashMap::new();
+    let mut visited: HashSet<N> = HashSet::new();
+    let mut stack = vec![(start.clone(), None)];
+
+    while let Some((node, parent)) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+
+        if let Some(parent) = parent {
+            parents.insert(node.clone(), parent);
+        }
+
+        if success(&node) {
+            return Some(reconstruct_path(&parents, node));
+        }
+
+        for next in successors(&node) {
+            if !visited.contains(&next) {
+                stack.push((next, Some(node.clone())));
+            }
+        }
+    }
+
+    None
+}
+
+/// Number of steps needed to reach every node at most `max_distance` steps away
+/// from `start`.
+pub fn bfs_distances_within<N, FN, IN>(
+    start: &N,
+    mut successors: FN,
+    max_distance: usize,
+) -> HashMap<N, usize>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    let mut distances: HashMap<N, usize> = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    distances.insert(start.clone(), 0);
+    queue.push_back((start.clone(), 0));
+
+    while let Some((node, distance)) = queue.pop_front() {
+        if distance >= max_distance {
+            continue;
+        }
+
+        for next in successors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// Number of steps needed to reach every reachable node from `start`.
+pub fn bfs_distances<N, FN, IN>(start: &N, successors: FN) -> HashMap<N, usize>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    bfs_distances_within(start, successors, usize::MAX)
+}
+
+/// Every node reachable from `start`, including itself.
+pub fn flood_fill<N, FN, IN>(start: &N, mut successors: FN) -> HashSet<N>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    let mut visited: HashSet<N> = HashSet::new();
+    let mut stack = vec![start.clone()];
+
+    visited.insert(start.clone());
+
+    while let Some(node) = stack.pop() {
+        for next in successors(&node) {
+            if visited.insert(next.clone()) {
+                stack.push(next);
+            }
+        }
+    }
+
+    visited
+}
+
+/// Groups `nodes` by connected component, in order of first appearance. The
+/// `successors` relation is expected to be symmetric.
+pub fn connected_components<N, I, FN, IN>(nodes: I, mut successors: FN) -> Vec<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    let mut seen: HashSet<N> = HashSet::new();
+    let mut components = vec![];
+
+    for node in nodes {
+        if seen.contains(&node) {
+            continue;
+        }
+
+        let component = flood_fill(&node, &mut successors);
+        seen.extend(component.iter().cloned());
+        components.push(component.into_iter().collect());
+    }
+
+    components
+}
+
+struct HeapEntry<C> {
+    estimate: C,
+    cost: C,
+    index: usize,
+}
+
+impl<C: PartialEq> PartialEq for HeapEntry<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate && self.cost == other.cost
+    }
+}
+
+impl<C: Eq> Eq for HeapEntry<C> {}
+
+impl<C: Ord> PartialOrd for HeapEntry<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Ord> Ord for HeapEntry<C> {
+    // Reversed so that the `BinaryHeap` pops the lowest estimate first, preferring
+    // the deepest node on ties.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+/// Every shortest path found by `dijkstra_all` or `astar_all`, stored as a graph of
+/// parents so that they don't have to be enumerated unless needed.
+pub struct ShortestPaths<N, C> {
+    pub cost: C,
+    nodes: Vec<N>,
+    parents: Vec<Vec<usize>>,
+    goals: Vec<usize>,
+}
+
+impl<N, C> ShortestPaths<N, C>
+where
+    N: Eq + Hash + Clone,
+{
+    /// The nodes matching `success` that were reached with the lowest cost.
+    pub fn goals(&self) -> impl Iterator<Item = &N> {
+        self.goals.iter().map(move |&i| &self.nodes[i])
+    }
+
+    /// One of the shortest paths.
+    pub fn path(&self) -> Vec<N> {
+        let mut path = vec![self.goals[0]];
+
+        while let Some(&parent) = self.parents[*path.last().unwrap()].first() {
+            path.push(parent);
+        }
+
+        path.iter().rev().map(|&i| self.nodes[i].clone()).collect()
+    }
+
+    /// All of the shortest paths. There can be exponentially many of them, prefer
+    /// `nodes` when only the cells lying on a shortest path matter.
+    pub fn paths(&self) -> Vec<Vec<N>> {
+        let mut paths = vec![];
+        let mut stack: Vec<Vec<usize>> = self.goals.iter().map(|&g| vec![g]).collect();
+
+        while let Some(partial) = stack.pop() {
+            let parents = &self.parents[*partial.last().unwrap()];
+
+            if parents.is_empty() {
+                paths.push(
+                    partial
+                        .iter()
+                        .rev()
+                        .map(|&i| self.nodes[i].clone())
+                        .collect(),
+                );
+                continue;
+            }
+
+            for &parent in parents {
+                let mut next = partial.clone();
+                next.push(parent);
+                stack.push(next);
+            }
+        }
+
+        paths
+    }
+
+    /// Every node lying on at least one of the shortest paths.
+    pub fn nodes(&self) -> HashSet<N> {
+        let mut visited: HashSet<usize> = self.goals.iter().copied().collect();
+        let mut stack = self.goals.clone();
+
+        while let Some(index) = stack.pop() {
+            for &parent in &self.parents[index] {
+                if visited.insert(parent) {
+                    stack.push(parent);
+                }
+            }
+        }
+
+        visited.into_iter().map(|i| self.nodes[i].clone()).collect()
+    }
+}
+
+/// Whether `ancestor` is on a path from `node` back to the start.
+fn is_ancestor(parents: &[Vec<usize>], ancestor: usize, node: usize) -> bool {
+    let mut visited = HashSet::from([node]);
+    let mut stack = vec![node];
+
+    while let Some(index) = stack.pop() {
+        if index == ancestor {
+            return true;
+        }
+
+        for &parent in &parents[index] {
+            if visited.insert(parent) {
+                stack.push(parent);
+            }
+        }
+    }
+
+    false
+}
+
+/// Shared implementation of Dijkstra and A*. When `success` is never matched, the
+/// whole reachable space is explored and `None` is returned along with the costs.
+fn best_first<N, C, FN, IN, FH, FS>(
+    start: &N,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+    all_paths: bool,
+) -> (Option<ShortestPaths<N, C>>, HashMap<N, C>)
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    let mut indices: HashMap<N, usize> = HashMap::new();
+    let mut nodes = vec![start.clone()];
+    let mut costs = vec![C::zero()];
+    let mut parents: Vec<Vec<usize>> = vec![vec![]];
+    let mut goals = vec![];
+    let mut best: Option<C> = None;
+    let mut heap = BinaryHeap::new();
+
+    indices.insert(start.clone(), 0);
+    heap.push(HeapEntry {
+        estimate: heuristic(start),
+        cost: C::zero(),
+        index: 0,
+    });
+
+    while let Some(HeapEntry {
+        estimate,
+        cost,
+        index,
+    }) = heap.pop()
+    {
+        // Entries left behind when a cheaper path was found. Nodes are expanded
+        // again when that happens, so inconsistent heuristics are fine.
+        if cost > costs[index] {
+            continue;
+        }
+
+        if let Some(best) = best {
+            if estimate > best {
+                break;
+            }
+        }
+
+        let node = nodes[index].clone();
+
+        if success(&node) {
+            best = Some(cost);
+            goals.push(index);
+
+            if !all_paths {
+                break;
+            }
+
+            continue;
+        }
+
+        for (next, step_cost) in successors(&node) {
+            let next_cost = cost + step_cost;
+
+            let next_index = match indices.get(&next) {
+                Some(&i) => {
+                    match next_cost.cmp(&costs[i]) {
+                        Ordering::Greater => continue,
+                        Ordering::Equal => {
+                            // Zero-cost edges can lead back to an ancestor, which
+                            // can't also be a descendant.
+                            if all_paths
+                                && !parents[i].contains(&index)
+                                && !is_ancestor(&parents, i, index)
+                            {
+                                parents[i].push(index);
+                            }
+
+                            continue;
+                        }
+                        Ordering::Less => {}
+                    }
+
+                    costs[i] = next_cost;
+                    parents[i] = vec![index];
+                    i
+                }
+                None => {
+                    let i = nodes.len();
+                    indices.insert(next.clone(), i);
+                    nodes.push(next.clone());
+                    costs.push(next_cost);
+                    parents.push(vec![index]);
+                    i
+                }
+            };
+
+            heap.push(HeapEntry {
+                estimate: next_cost + heuristic(&next),
+                cost: next_cost,
+                index: next_index,
+            });
+        }
+    }
+
+    let result = best.map(|cost| ShortestPaths {
+        cost,
+        nodes: nodes.clone(),
+        parents,
+        goals,
+    });
+
+    let costs = nodes.into_iter().zip(costs).collect();
+
+    (result, costs)
+}
+
+/// Cheapest path from `start` to the first node matching `success`, along with its cost.
+pub fn dijkstra<N, C, FN, IN, FS>(start: &N, successors: FN, success: FS) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    astar(start, successors, |_| C::zero(), success)
+}
+
+/// Same as `dijkstra`, guided by a `heuristic` that must never overestimate the
+/// remaining cost.
+pub fn astar<N, C, FN, IN, FH, FS>(
+    start: &N,
+    successors: FN,
+    heuristic: FH,
+    success: FS,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    best_first(start, successors, heuristic, success, false)
+        .0
+        .map(|paths| (paths.path(), paths.cost))
+}
+
+/// Every cheapest path from `start` to any node matching `success`.
+pub fn dijkstra_all<N, C, FN, IN, FS>(
+    start: &N,
+    successors: FN,
+    success: FS,
+) -> Option<ShortestPaths<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    astar_all(start, successors, |_| C::zero(), success)
+}
+
+pub fn astar_all<N, C, FN, IN, FH, FS>(
+    start: &N,
+    successors: FN,
+    heuristic: FH,
+    success: FS,
+) -> Option<ShortestPaths<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    best_first(start, successors, heuristic, success, true).0
+}
+
+/// Cost of the cheapest path from `start` to every reachable node.
+pub fn dijkstra_distances<N, C, FN, IN>(start: &N, successors: FN) -> HashMap<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+{
+    best_first(start, successors, |_| C::zero(), |_| false, false).1
+}
+
+type Pos = (usize, usize);
+
+/// Orthogonal neighbours of `pos` that can be moved to according to `connected`,
+/// which is given the values of the current and of the neighbouring cell.
+pub fn grid_successors<'a, T, F>(
+    grid: &'a Grid<T>,
+    pos: Pos,
+    connected: &'a F,
+) -> impl Iterator<Item = Pos> + 'a
+where
+    F: Fn(&T, &T) -> bool,
+{
+    let from = grid.get(pos.0, pos.1);
+
+    grid.ortho_neighbour_positions(pos.0, pos.1)
+        .filter(move |&(x, y)| match (from, grid.get(x, y)) {
+            (Some(from), Some(to)) => connected(from, to),
+            _ => false,
+        })
+}
+
+pub fn grid_bfs<T, F>(grid: &Grid<T>, start: Pos, goal: Pos, connected: F) -> Option<Vec<Pos>>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    bfs(
+        &start,
+        |&pos| grid_successors(grid, pos, &connected),
+        |&pos| pos == goal,
+    )
+}
+
+pub fn grid_distances<T, F>(grid: &Grid<T>, start: Pos, connected: F) -> HashMap<Pos, usize>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    bfs_distances(&start, |&pos| grid_successors(grid, pos, &connected))
+}
+
+pub fn grid_flood_fill<T, F>(grid: &Grid<T>, start: Pos, connected: F) -> HashSet<Pos>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    flood_fill(&start, |&pos| grid_successors(grid, pos, &connected))
+}
+
+/// Connected components of the grid, in reading order of their first cell.
+pub fn grid_components<T, F>(grid: &Grid<T>, connected: F) -> Vec<Vec<Pos>>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    let positions = (0..grid.rows()).flat_map(|y| (0..grid.cols()).map(move |x| (x, y)));
+
+    connected_components(positions, |&pos| grid_successors(grid, pos, &connected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn maze() -> Grid<char> {
+        let mut grid = Grid::new();
+
+        for l in ["S..#....", ".#.#.##.", ".#...#..", ".####.#.", "......#E"] {
+            grid.push_row(l.chars().collect());
+        }
+
+        grid
+    }
+
+    fn open(_: &char, to: &char) -> bool {
+        *to != '#'
+    }
+
+    #[test]
+    fn test_bfs_and_dfs() {
+        let grid = maze();
+
+        let path = grid_bfs(&grid, (0, 0), (7, 4), open).unwrap();
+        assert_eq!(15, path.len() - 1);
+        assert_eq!(Some(&(0, 0)), path.first());
+        assert_eq!(Some(&(7, 4)), path.last());
+
+        let path = dfs(
+            &(0, 0),
+            |&pos| grid_successors(&grid, pos, &open).collect::<Vec<_>>(),
+            |&pos| pos == (7, 4),
+        )
+        .unwrap();
+        assert!(path.windows(2).all(|w| {
+            let ((ax, ay), (bx, by)) = (w[0], w[1]);
+            ax.abs_diff(bx) + ay.abs_diff(by) == 1
+        }));
+
+        assert_eq!(None, grid_bfs(&grid, (0, 0), (3, 0), open));
+    }
+
+    #[test]
+    fn test_distances_and_components() {
+        let grid = maze();
+
+        let distances = grid_distances(&grid, (0, 0), open);
+        assert_eq!(Some(&4), distances.get(&(2, 2)));
+        assert_eq!(None, distances.get(&(3, 0)));
+
+        let components = grid_components(&grid, |a, b| (*a == '#') == (*b == '#'));
+        assert_eq!(5, components.len());
+        assert_eq!(
+            grid_flood_fill(&grid, (0, 0), open).len(),
+            components[0].len()
+        );
+
+        let reached = bfs_distances_within(&0i32, |&n| vec![n - 1, n + 1], 3);
+        assert_eq!(7, reached.len());
+    }
+
+    #[test]
+    fn test_weighted() {
+        // a -1-> b -1-> d
+        // a -2-> c -0-> d
+        // a -5-> d
+        let successors = |n: &char| match n {
+            'a' => vec![('b', 1), ('c', 2), ('d', 5)],
+            'b' => vec![('d', 1)],
+            'c' => vec![('d', 0)],
+            _ => vec![],
+        };
+
+        assert_eq!(
+            Some((vec!['a', 'b', 'd'], 2)),
+            dijkstra(&'a', successors, |&n| n == 'd')
+        );
+
+        let all = dijkstra_all(&'a', successors, |&n| n == 'd').unwrap();
+        assert_eq!(2, all.cost);
+        assert_eq!(2, all.paths().len());
+        assert_eq!(4, all.nodes().len());
+
+        let distances = dijkstra_distances(&'a', successors);
+        assert_eq!(Some(&2), distances.get(&'d'));
+        assert_eq!(Some(&2), distances.get(&'c'));
+    }
+
+    #[test]
+    fn test_zero_cost_cycle() {
+        // a -1-> b -0-> c -1-> d
+        //        b <-0- c
+        let successors = |n: &char| match n {
+            'a' => vec![('b', 1)],
+            'b' => vec![('c', 0)],
+            'c' => vec![('b', 0), ('d', 1)],
+            _ => vec![],
+        };
+
+        let all = dijkstra_all(&'a', successors, |&n| n == 'd').unwrap();
+        assert_eq!(2, all.cost);
+        assert_eq!(vec!['a', 'b', 'c', 'd'], all.path());
+        assert_eq!(vec![vec!['a', 'b', 'c', 'd']], all.paths());
+        assert_eq!(4, all.nodes().len());
+    }
+
+    #[test]
+    fn test_equal_cost_parents() {
+        // a -1-> b
+        // a -1-> c -0-> b
+        let successors = |n: &char| match n {
+            'a' => vec![('b', 1), ('c', 1)],
+            'c' => vec![('b', 0)],
+            _ => vec![],
+        };
+
+        let all = dijkstra_all(&'a', successors, |&n| n == 'b').unwrap();
+        assert_eq!(1, all.cost);
+
+        let mut paths = all.paths();
+        paths.sort();
+        assert_eq!(vec![vec!['a', 'b'], vec!['a', 'c', 'b']], paths);
+    }
+
+    #[test]
+    fn test_inconsistent_heuristic() {
+        // s -1-> a -1-> c -10-> g
+        // s -1-> b -2-> c
+        let successors = |n: &char| match n {
+            's' => vec![('a', 1), ('b', 1)],
+            'a' => vec![('c', 1)],
+            'b' => vec![('c', 2)],
+            'c' => vec![('g', 10)],
+            _ => vec![],
+        };
+        // Never overestimates, but makes `c` reached through `b` first.
+        let heuristic = |n: &char| if *n == 'a' { 3 } else { 0 };
+
+        assert_eq!(
+            Some((vec!['s', 'a', 'c', 'g'], 12)),
+            astar(&'s', successors, heuristic, |&n| n == 'g')
+        );
+    }
+
+    #[test]
+    fn test_astar() {
+        let goal = (20i32, 20i32);
+        let successors = |&(x, y): &(i32, i32)| {
+            vec![
+                ((x + 1, y), 1),
+                ((x, y + 1), 1),
+                ((x - 1, y), 1),
+                ((x, y - 1), 1),
+            ]
+            .into_iter()
+            .filter(|((x, y), _)| *x >= 0 && *y >= 0 && !(*x == 10 && *y < 15))
+        };
+
+        let (path, cost) = astar(
+            &(0, 0),
+            successors,
+            |&(x, y)| (goal.0 - x).abs() + (goal.1 - y).abs(),
+            |&pos| pos == goal,
+        )
+        .unwrap();
+
+        assert_eq!(40, cost);
+        assert_eq!(41, path.len());
+    }
+}
@@ -0,0 +1,186 @@
+#![allow(unused)]
+
+use itertools::Itertools;
+use num::{rational::Ratio, Integer};
+
+use crate::utils::math::Int;
+
+/// The sequence, its differences, the differences of those, etc. until they're all the
+/// same value (or there is a single one left).
+pub fn difference_table<T: Int>(values: &[T]) -> Vec<Vec<T>> {
+    let mut table = vec![values.to_vec()];
+
+    loop {
+        let last = table.last().unwrap();
+        if last.len() <= 1 || last.iter().all_equal() {
+            break;
+        }
+
+        let next = last.iter().tuple_windows().map(|(&a, &b)| b - a).collect();
+        table.push(next);
+    }
+
+    table
+}
+
+/// Next value of a sequence given by a polynomial, from its finite differences.
+pub fn extrapolate_next<T: Int>(values: &[T]) -> T {
+    difference_table(values)
+        .iter()
+        .rev()
+        .fold(T::zero(), |acc, s| acc + *s.last().unwrap_or(&T::zero()))
+}
+
+/// Value before the first one of a sequence given by a polynomial.
+pub fn extrapolate_previous<T: Int>(values: &[T]) -> T {
+    difference_table(values)
+        .iter()
+        .rev()
+        .fold(T::zero(), |acc, s| *s.first().unwrap_or(&T::zero()) - acc)
+}
+
+/// Value at `x` of the polynomial going through every `(x, y)` point, computed exactly.
+pub fn interpolate<T: Int + Integer>(points: &[(T, T)], x: T) -> Ratio<T> {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, &(xi, yi))| {
+            points
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| i != j)
+                .fold(Ratio::from_integer(yi), |acc, (_, &(xj, _))| {
+                    acc * Ratio::new(x - xj, xi - xj)
+                })
+        })
+        .fold(Ratio::from_integer(T::zero()), |acc, term| acc + term)
+}
+
+/// How a sequence behaves from its `start`-th value on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pattern {
+    /// `values[i + period] == values[i]`.
+    Periodic { start: usize, period: usize },
+    /// The differences of order `degree` are constant.
+    Polynomial { start: usize, degree: usize },
+}
+
+/// Smallest period that repeats at least `min_repeats` times at the end of the values.
+pub fn find_period<T: PartialEq>(values: &[T], min_repeats: usize) -> Option<Pattern> {
+    (1..=values.len() / min_repeats.max(1)).find_map(|period| {
+        let start = (0..values.len() - period)
+            .rev()
+            .find(|&i| values[i] != values[i + period])
+            .map_or(0, |i| i + 1);
+
+        if values.len() - start >= period * min_repeats {
+            Some(Pattern::Periodic { start, period })
+        } else {
+            None
+        }
+    })
+}
+
+/// Lowest degree whose differences end with at least `min_repeats` equal values.
+pub fn find_polynomial<T: Int>(values: &[T], min_repeats: usize) -> Option<Pattern> {
+    let min_repeats = min_repeats.max(1);
+
+    difference_table(values)
+        .iter()
+        .enumerate()
+        .find_map(|(degree, differences)| {
+            let last = differences.last()?;
+            let constant = differences.iter().rev().take_while(|&v| v == last).count();
+
+            if constant >= min_repeats {
+                Some(Pattern::Polynomial {
+                    start: differences.len() - constant,
+                    degree,
+                })
+            } else {
+                None
+            }
+        })
+}
+
+/// A period if there is one, a polynomial otherwise.
+pub fn detect<T: Int>(values: &[T], min_repeats: usize) -> Option<Pattern> {
+    find_period(values, min_repeats).or_else(|| find_polynomial(values, min_repeats))
+}
+
+impl Pattern {
+    /// Value at index `n` of a sequence following the pattern, which starts with
+    /// `values`.
+    pub fn value_at<T: Int + Integer>(&self, values: &[T], n: usize) -> T {
+        if n < values.len() {
+            return values[n];
+        }
+
+        match *self {
+            Self::Periodic { start, period } => values[start + (n - start) % period],
+            Self::Polynomial { degree, .. } => {
+                let points = (values.len() - degree - 1..values.len())
+                    .map(|i| (T::from(i).unwrap(), values[i]))
+                    .collect_vec();
+
+                interpolate(&points, T::from(n).unwrap()).to_integer()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extrapolation() {
+        let values = [10, 13, 16, 21, 30, 45];
+
+        assert_eq!(4, difference_table(&values).len());
+        assert_eq!(68, extrapolate_next(&values));
+        assert_eq!(5, extrapolate_previous(&values));
+        assert_eq!(7, extrapolate_next(&[7]));
+    }
+
+    #[test]
+    fn test_interpolate() {
+        // y = x^2 / 2
+        let points = [(0_i64, 0), (2, 2), (4, 8)];
+
+        assert_eq!(Ratio::new(9, 2), interpolate(&points, 3));
+        assert_eq!(
+            Ratio::from_integer(200_000_000_000_000_000_000_i128),
+            interpolate(&[(0_i128, 0), (2, 2), (4, 8)], 20_000_000_000)
+        );
+    }
+
+    #[test]
+    fn test_detect() {
+        let periodic = [5, 1, 2, 3, 1, 2, 3, 1, 2];
+        let pattern = detect(&periodic, 2).unwrap();
+
+        assert_eq!(
+            Pattern::Periodic {
+                start: 1,
+                period: 3
+            },
+            pattern
+        );
+        assert_eq!(1, pattern.value_at(&periodic, 1_000_000));
+
+        // Squares after some noise.
+        let squares = [7, -3, 4, 9, 16, 25, 36, 49];
+        let pattern = detect(&squares, 3).unwrap();
+
+        assert_eq!(
+            Pattern::Polynomial {
+                start: 2,
+                degree: 2
+            },
+            pattern
+        );
+        assert_eq!(10_000_i64.pow(2), pattern.value_at(&squares, 10_000));
+        assert_eq!(None, detect(&[1, 5, 2, 8], 2));
+    }
+}
@@ -0,0 +1,180 @@
+#![allow(unused)]
+
+use std::{
+    fmt::Display,
+    io::Write,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    thread,
+    time::Duration,
+};
+
+use colored::{ColoredString, Colorize};
+
+use super::grid::{sparse::SparseGrid, Grid};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static DELAY_MS: AtomicU64 = AtomicU64::new(0);
+
+/// Turns visualizations on, which only the `--visualize` flag should do, so that
+/// nothing is drawn when running every day or the tests.
+pub fn enable(delay: Duration) {
+    DELAY_MS.store(delay.as_millis() as u64, Ordering::Relaxed);
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether to draw anything. Solvers should check it before building frames that
+/// are costly to make.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Time each frame stays on screen.
+pub fn delay() -> Duration {
+    Duration::from_millis(DELAY_MS.load(Ordering::Relaxed))
+}
+
+/// Anything that can be drawn cell by cell, from the top-left corner.
+pub trait Frame {
+    type Cell;
+
+    /// `(width, height)`
+    fn dims(&self) -> (usize, usize);
+
+    /// `None` for missing cells, e.g. holes of a `SparseGrid`.
+    fn cell(&self, x: usize, y: usize) -> Option<&Self::Cell>;
+}
+
+impl<T> Frame for Grid<T> {
+    type Cell = T;
+
+    fn dims(&self) -> (usize, usize) {
+        (self.cols(), self.rows())
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Option<&T> {
+        self.get(x, y)
+    }
+}
+
+/// Drawn over its bounding box.
+impl<T> Frame for SparseGrid<T> {
+    type Cell = T;
+
+    fn dims(&self) -> (usize, usize) {
+        (self.cols(), self.rows())
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Option<&T> {
+        let ((min_x, min_y), _) = self.bounds()?;
+
+        self.get((min_x + x as isize, min_y + y as isize))
+    }
+}
+
+/// Rows of cells, which don't have to be the same length.
+impl<T> Frame for [Vec<T>] {
+    type Cell = T;
+
+    fn dims(&self) -> (usize, usize) {
+        (self.iter().map(Vec::len).max().unwrap_or(0), self.len())
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Option<&T> {
+        self.get(y)?.get(x)
+    }
+}
+
+/// The frame as lines of text, `style` giving the (colored) text of every cell.
+pub fn render<F, S>(frame: &F, mut style: S) -> String
+where
+    F: Frame + ?Sized,
+    S: FnMut(Option<&F::Cell>) -> ColoredString,
+{
+    let (width, height) = frame.dims();
+    let mut s = String::new();
+
+    for y in 0..height {
+        for x in 0..width {
+            s.push_str(&style(frame.cell(x, y)).to_string());
+        }
+
+        s.push('\n');
+    }
+
+    s
+}
+
+/// Style of the frames made of characters, e.g. from the input.
+pub fn plain(c: Option<&char>) -> ColoredString {
+    c.map_or(' ', |&c| c).to_string().normal()
+}
+
+/// Style of the frames made of lit and unlit pixels.
+pub fn pixels(lit: Option<&bool>) -> ColoredString {
+    if lit == Some(&true) {
+        "█".normal()
+    } else {
+        " ".normal()
+    }
+}
+
+/// Draws a frame in place of the previous one and waits for the playback delay. Does
+/// nothing unless visualizations are enabled.
+pub fn show<F, S>(title: impl Display, frame: &F, style: S)
+where
+    F: Frame + ?Sized,
+    S: FnMut(Option<&F::Cell>) -> ColoredString,
+{
+    if !enabled() {
+        return;
+    }
+
+    // Clear the screen and move the cursor back to the top-left corner.
+    print!(
+        "\x1B[2J\x1B[H{}\n{}",
+        title.to_string().bold(),
+        render(frame, style)
+    );
+    std::io::stdout().flush().ok();
+
+    thread::sleep(delay());
+}
+
+/// Shows every frame in turn, with their index as title.
+pub fn play<I, F, S>(frames: I, mut style: S)
+where
+    I: IntoIterator<Item = F>,
+    F: Frame,
+    S: FnMut(Option<&F::Cell>) -> ColoredString,
+{
+    if !enabled() {
+        return;
+    }
+
+    for (i, frame) in frames.into_iter().enumerate() {
+        show(format!("Frame {}", i), &frame, &mut style);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let grid = Grid::from_data(3, vec!['#', '.', '#', '.', '#', '.']);
+        assert_eq!("#.#\n.#.\n", render(&grid, plain));
+
+        let sparse: SparseGrid<bool> = vec![((-1, 5), true), ((1, 6), true)].into_iter().collect();
+        assert_eq!((3, 2), sparse.dims());
+        assert_eq!("█  \n  █\n", render(&sparse, pixels));
+
+        let rows = vec![vec![1, 2], vec![3]];
+        assert_eq!(
+            "12\n3?\n",
+            render(rows.as_slice(), |v| v
+                .map_or("?".to_string(), ToString::to_string)
+                .normal())
+        );
+    }
+}
@@ -0,0 +1,528 @@
+#![allow(unused)]
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Debug,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use crate::utils::cycle::brent;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Registers(pub Vec<i64>);
+
+impl Registers {
+    pub fn new(count: usize) -> Self {
+        Self(vec![0; count])
+    }
+
+    /// Index of a register named by a lowercase letter, `a` being 0.
+    pub fn index_of(name: char) -> Option<usize> {
+        if name.is_ascii_lowercase() {
+            Some((name as u8 - b'a') as usize)
+        } else {
+            None
+        }
+    }
+}
+
+impl Index<usize> for Registers {
+    type Output = i64;
+
+    fn index(&self, index: usize) -> &i64 {
+        &self.0[index]
+    }
+}
+
+impl IndexMut<usize> for Registers {
+    fn index_mut(&mut self, index: usize) -> &mut i64 {
+        &mut self.0[index]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Operand {
+    Register(usize),
+    Value(i64),
+}
+
+impl Operand {
+    pub fn value(self, registers: &Registers) -> i64 {
+        match self {
+            Self::Register(r) => registers[r],
+            Self::Value(v) => v,
+        }
+    }
+
+    pub fn register(self) -> Option<usize> {
+        match self {
+            Self::Register(r) => Some(r),
+            Self::Value(_) => None,
+        }
+    }
+}
+
+/// Parses either a register named by a lowercase letter, or a number.
+impl FromStr for Operand {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_lowercase() => {
+                Ok(Self::Register(Registers::index_of(c).unwrap()))
+            }
+            _ => s.parse().map(Self::Value).map_err(|_| ()),
+        }
+    }
+}
+
+/// What to do after an instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Flow {
+    Next,
+    /// Relative jump.
+    Jump(i64),
+    /// Absolute jump.
+    Goto(i64),
+    /// Outputs a value, then goes to the next instruction.
+    Output(i64),
+    Halt,
+}
+
+/// An instruction set. Instructions are cloned before being executed, so they can
+/// modify the program they're part of.
+pub trait Instruction: Clone + Debug {
+    fn execute(&self, machine: &mut Machine<Self>) -> Flow;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Running,
+    Output(i64),
+    Halted,
+}
+
+/// Replaces the execution of a known sequence of instructions starting at the given
+/// program counter, e.g. a multiplication loop. Returns the program counter to
+/// continue from, or `None` when the sequence doesn't match.
+pub type Peephole<I> = Box<dyn Fn(&[I], usize, &mut Registers) -> Option<usize>>;
+
+/// State of a machine whose program repeats forever, found by `find_cycle`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// Number of steps before entering the cycle.
+    pub start: usize,
+    pub length: usize,
+    /// Everything output until the state repeated, the values output in the cycle
+    /// starting at `output_start`.
+    pub outputs: Vec<i64>,
+    pub output_start: usize,
+}
+
+/// Everything deciding what the machine does next, compared to find cycles. The
+/// program is only known by its version, so that it isn't copied at every step.
+#[derive(Clone, PartialEq, Eq)]
+struct State {
+    pc: usize,
+    halted: bool,
+    registers: Registers,
+    version: usize,
+}
+
+pub struct Machine<I> {
+    pub registers: Registers,
+    program: Vec<I>,
+    pc: usize,
+    halted: bool,
+    version: usize,
+    pc_register: Option<usize>,
+    peepholes: Vec<Peephole<I>>,
+    steps: usize,
+    hits: Vec<usize>,
+    trace: VecDeque<(usize, I)>,
+    trace_limit: usize,
+}
+
+impl<I: Instruction> Machine<I> {
+    pub fn new(program: Vec<I>, register_count: usize) -> Self {
+        let len = program.len();
+
+        Self {
+            registers: Registers::new(register_count),
+            program,
+            pc: 0,
+            halted: false,
+            version: 0,
+            pc_register: None,
+            peepholes: vec![],
+            steps: 0,
+            hits: vec![0; len],
+            trace: VecDeque::new(),
+            trace_limit: 0,
+        }
+    }
+
+    /// Binds the program counter to a register, like the `#ip` directive of ElfCode:
+    /// the register is set to the program counter before each instruction, and read
+    /// back after it.
+    pub fn with_pc_register(mut self, register: usize) -> Self {
+        self.pc_register = Some(register);
+        self
+    }
+
+    /// Peepholes are tried in order before each instruction. A matching one counts
+    /// as a single step.
+    pub fn add_peephole(&mut self, peephole: Peephole<I>) {
+        self.peepholes.push(peephole);
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn set_pc(&mut self, pc: usize) {
+        self.pc = pc;
+    }
+
+    pub fn program(&self) -> &[I] {
+        &self.program
+    }
+
+    pub fn instruction(&self, index: usize) -> Option<&I> {
+        self.program.get(index)
+    }
+
+    /// Replaces an instruction, for self-modifying programs.
+    pub fn set_instruction(&mut self, index: usize, instruction: I) {
+        self.program[index] = instruction;
+        self.version += 1;
+    }
+
+    /// Incremented every time the program is modified.
+    pub fn program_version(&self) -> usize {
+        self.version
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted || self.pc >= self.program.len()
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Number of times each instruction was executed.
+    pub fn hits(&self) -> &[usize] {
+        &self.hits
+    }
+
+    /// Keeps the last `limit` executed instructions, along with their address.
+    pub fn enable_trace(&mut self, limit: usize) {
+        self.trace_limit = limit;
+        while self.trace.len() > limit {
+            self.trace.pop_front();
+        }
+    }
+
+    pub fn trace(&self) -> impl Iterator<Item = &(usize, I)> {
+        self.trace.iter()
+    }
+
+    fn jump_to(&mut self, target: i64) {
+        if target < 0 {
+            self.halted = true;
+        } else {
+            self.pc = target as usize;
+        }
+    }
+
+    pub fn step(&mut self) -> Status {
+        if self.is_halted() {
+            return Status::Halted;
+        }
+
+        let pc = self.pc;
+        self.steps += 1;
+        self.hits[pc] += 1;
+
+        let (program, registers) = (&self.program, &mut self.registers);
+        if let Some(next) = self
+            .peepholes
+            .iter()
+            .find_map(|p| p(program, pc, registers))
+        {
+            self.pc = next;
+            return Status::Running;
+        }
+
+        if let Some(r) = self.pc_register {
+            self.registers[r] = pc as i64;
+        }
+
+        let instruction = self.program[pc].clone();
+        if self.trace_limit > 0 {
+            if self.trace.len() == self.trace_limit {
+                self.trace.pop_front();
+            }
+            self.trace.push_back((pc, instruction.clone()));
+        }
+
+        let flow = instruction.execute(self);
+
+        let pc = match self.pc_register {
+            Some(r) if flow == Flow::Next => self.registers[r],
+            _ => pc as i64,
+        };
+
+        match flow {
+            Flow::Next => self.jump_to(pc + 1),
+            Flow::Jump(offset) => self.jump_to(pc + offset),
+            Flow::Goto(target) => self.jump_to(target),
+            Flow::Output(value) => {
+                self.jump_to(pc + 1);
+                return Status::Output(value);
+            }
+            Flow::Halt => self.halted = true,
+        }
+
+        if self.is_halted() {
+            Status::Halted
+        } else {
+            Status::Running
+        }
+    }
+
+    /// Runs until the machine outputs a value or halts.
+    pub fn run(&mut self) -> Status {
+        loop {
+            match self.step() {
+                Status::Running => {}
+                status => return status,
+            }
+        }
+    }
+
+    /// Runs until the machine halts, and returns everything it output.
+    pub fn run_to_end(&mut self) -> Vec<i64> {
+        let mut outputs = vec![];
+
+        while let Status::Output(value) = self.run() {
+            outputs.push(value);
+        }
+
+        outputs
+    }
+
+    /// Runs until an instruction is about to be executed a second time, and returns
+    /// its address. Returns `None` if the machine halts first.
+    pub fn run_until_repeat(&mut self) -> Option<usize> {
+        while !self.is_halted() {
+            if self.hits[self.pc] > 0 {
+                return Some(self.pc);
+            }
+
+            self.step();
+        }
+
+        None
+    }
+
+    fn state(&self) -> State {
+        State {
+            pc: self.pc,
+            halted: self.halted,
+            registers: self.registers.clone(),
+            version: self.version,
+        }
+    }
+
+    /// `programs` holds the program of every version seen.
+    fn set_state(&mut self, state: &State, programs: &HashMap<usize, Vec<I>>) {
+        if state.version != self.version {
+            self.program = programs[&state.version].clone();
+            self.version = state.version;
+        }
+
+        self.pc = state.pc;
+        self.halted = state.halted;
+        self.registers = state.registers.clone();
+    }
+
+    /// Runs until the whole state of the machine (program counter, registers and
+    /// program) repeats. Returns `None` if the machine halts first. A modified program
+    /// counts as a new state, even if it's modified back.
+    pub fn find_cycle(&mut self) -> Option<Cycle> {
+        // Brent's algorithm only keeps a couple of states, but it goes back and forth
+        // between them: the machine is rewound once the cycle is known, then run
+        // through it once more to count the steps and gather the outputs.
+        let start_state = self.state();
+        let (steps, hits, trace) = (self.steps, self.hits.clone(), self.trace.clone());
+        let mut programs = HashMap::new();
+        programs.insert(self.version, self.program.clone());
+
+        // A halted machine is a cycle of length 1, told apart when running it again.
+        let found = brent(&start_state, |state| {
+            self.set_state(state, &programs);
+            self.step();
+
+            if self.version != state.version {
+                programs
+                    .entry(self.version)
+                    .or_insert_with(|| self.program.clone());
+            }
+
+            self.state()
+        });
+
+        self.set_state(&start_state, &programs);
+        self.steps = steps;
+        self.hits = hits;
+        self.trace = trace;
+
+        let mut outputs = vec![];
+        let mut output_start = 0;
+
+        for i in 0..found.start + found.length {
+            if self.is_halted() {
+                return None;
+            }
+
+            if i == found.start {
+                output_start = outputs.len();
+            }
+
+            if let Status::Output(value) = self.step() {
+                outputs.push(value);
+            }
+        }
+
+        Some(Cycle {
+            start: steps + found.start,
+            length: found.length,
+            outputs,
+            output_start,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The console of 2020 day 8, with an extra `out` instruction.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    enum Console {
+        Acc(i64),
+        Jmp(i64),
+        Nop,
+        Out,
+    }
+
+    impl Instruction for Console {
+        fn execute(&self, machine: &mut Machine<Self>) -> Flow {
+            match self {
+                Self::Acc(v) => {
+                    machine.registers[0] += v;
+                    Flow::Next
+                }
+                Self::Jmp(offset) => Flow::Jump(*offset),
+                Self::Nop => Flow::Next,
+                Self::Out => Flow::Output(machine.registers[0]),
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_until_repeat() {
+        use Console::{Acc, Jmp, Nop};
+
+        let program = vec![
+            Nop,
+            Acc(1),
+            Jmp(4),
+            Acc(3),
+            Jmp(-3),
+            Acc(-99),
+            Acc(1),
+            Jmp(-4),
+            Acc(6),
+        ];
+
+        let mut m = Machine::new(program.clone(), 1);
+        assert_eq!(Some(1), m.run_until_repeat());
+        assert_eq!(5, m.registers[0]);
+
+        let mut fixed = program;
+        fixed[7] = Nop;
+        let mut m = Machine::new(fixed, 1);
+        assert_eq!(None, m.run_until_repeat());
+        assert_eq!(8, m.registers[0]);
+    }
+
+    #[test]
+    fn test_cycle_and_trace() {
+        use Console::{Acc, Jmp, Out};
+
+        let mut m = Machine::new(vec![Acc(1), Out, Acc(-1), Out, Jmp(-4)], 1);
+        m.enable_trace(2);
+
+        assert_eq!(Status::Output(1), m.run());
+        assert_eq!(
+            vec![(0, Acc(1)), (1, Out)],
+            m.trace().cloned().collect::<Vec<_>>()
+        );
+
+        let cycle = m.find_cycle().unwrap();
+        assert_eq!((2, 5), (cycle.start, cycle.length));
+        assert_eq!(vec![0, 1], cycle.outputs);
+        assert_eq!(0, cycle.output_start);
+        assert_eq!(&[2, 2, 1, 1, 1], m.hits());
+    }
+
+    #[test]
+    fn test_peephole_and_pc_register() {
+        use Console::{Acc, Jmp, Out};
+
+        // Would loop forever adding 1 to the accumulator, the peephole adds 1000 in
+        // a single step instead, then jumps to the output.
+        let program = vec![Acc(1), Jmp(-1), Out];
+        let mut m = Machine::new(program, 2);
+
+        m.add_peephole(Box::new(|program, pc, registers| {
+            if pc == 0 && program[1] == Jmp(-1) {
+                registers[0] += 1000;
+                return Some(2);
+            }
+            None
+        }));
+        assert_eq!(Status::Output(1000), m.run());
+        assert_eq!(2, m.steps());
+
+        // Skips the jump by moving the program counter through its register.
+        #[derive(Clone, Debug)]
+        struct AddIp(i64);
+        impl Instruction for AddIp {
+            fn execute(&self, machine: &mut Machine<Self>) -> Flow {
+                machine.registers[1] += self.0;
+                Flow::Next
+            }
+        }
+
+        let mut m = Machine::new(vec![AddIp(1), AddIp(100), AddIp(0)], 2).with_pc_register(1);
+        assert_eq!(Status::Halted, m.run());
+        assert_eq!(2, m.steps());
+    }
+
+    #[test]
+    fn test_operand() {
+        let registers = Registers(vec![5, 7]);
+
+        assert_eq!(Ok(Operand::Register(1)), "b".parse());
+        assert_eq!(Ok(Operand::Value(-3)), "-3".parse());
+        assert_eq!(Err(()), "ab".parse::<Operand>());
+        assert_eq!(7, Operand::Register(1).value(&registers));
+    }
+}
@@ -1,23 +1,36 @@
+use itertools::Itertools;
+
+use crate::utils::math::mod_pow;
+
 pub struct Day25;
 
-crate::impl_day!("25", false);
+crate::impl_day!("25", true);
 
-fn process_input(input: &str) -> &str {
+/// Row and column of the code.
+fn process_input(input: &str) -> (i64, i64) {
     input
+        .split(|c: char| !c.is_ascii_digit())
+        .flat_map(str::parse)
+        .collect_tuple()
+        .unwrap()
+}
+
+/// Codes are generated along the diagonals, each one from the previous one.
+fn code_at(row: i64, column: i64) -> i64 {
+    let diagonal = row + column - 1;
+    let index = diagonal * (diagonal - 1) / 2 + column - 1;
+
+    20_151_125 * mod_pow(252_533, index, 33_554_393) % 33_554_393
 }
 
-#[allow(unused)]
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    let (row, column) = process_input(input);
 
-    let res = "Part 1 not done";
-    Box::new(res)
+    Box::new(code_at(row, column))
 }
 
 #[allow(unused)]
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
-
     let res = "Part 2 not done";
     Box::new(res)
 }
@@ -26,15 +39,18 @@ fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
 mod tests {
     use super::*;
 
-    const INPUT: &str = r"";
+    const INPUT: &str = r"To continue, please consult the code grid in the manual.  Enter the code at row 4, column 2.";
 
     #[test]
     fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
+        assert_eq!(32_451_966.to_string(), *solve_part1(INPUT).to_string());
     }
 
     #[test]
-    fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
+    fn test_codes() {
+        assert_eq!(20_151_125, code_at(1, 1));
+        assert_eq!(31_916_031, code_at(2, 1));
+        assert_eq!(17_289_845, code_at(1, 3));
+        assert_eq!(27_995_004, code_at(6, 6));
     }
 }
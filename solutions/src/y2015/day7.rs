@@ -1,15 +1,13 @@
-use std::{collections::HashMap, convert::TryFrom};
+use std::collections::HashMap;
 
-use anyhow::anyhow;
-use lazy_static::lazy_static;
-use regex::Regex;
+use crate::utils::parse::{parse_lines, Line, ParseError};
 
 pub struct Day7;
 
 crate::impl_day!("7", true);
 
 fn process_input(input: &str) -> Vec<Instruction> {
-    input.lines().flat_map(Instruction::try_from).collect()
+    parse_lines(input, Instruction::parse).unwrap_or_else(|e| panic!("{}", e))
 }
 
 type State = HashMap<String, Option<i32>>;
@@ -26,149 +24,45 @@ enum Operation {
     BitwiseWithOne(String),
 }
 
-lazy_static! {
-    static ref RE_ASSIGNMENT: Regex = Regex::new(r"^(\d+|[a-z]+) -> ([a-z]+)$").unwrap();
-    static ref RE_AND: Regex = Regex::new(r"^([a-z]+) AND ([a-z]+) -> ([a-z]+)$").unwrap();
-    static ref RE_OR: Regex = Regex::new(r"^([a-z]+) OR ([a-z]+) -> ([a-z]+)$").unwrap();
-    static ref RE_LSHIFT: Regex = Regex::new(r"^([a-z]+) LSHIFT (\d+) -> ([a-z]+)$").unwrap();
-    static ref RE_RSHIFT: Regex = Regex::new(r"^([a-z]+) RSHIFT (\d+) -> ([a-z]+)$").unwrap();
-    static ref RE_NOT: Regex = Regex::new(r"^NOT ([a-z]+) -> ([a-z]+)$").unwrap();
-    static ref RE_COPY: Regex = Regex::new(r"^1 AND ([a-z]+) -> ([a-z]+)$").unwrap();
-}
-
 #[derive(Debug)]
 struct Instruction(Operation, String);
 
-impl TryFrom<&str> for Instruction {
-    type Error = anyhow::Error;
-
-    #[allow(clippy::too_many_lines)]
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
+impl Instruction {
+    fn parse(line: Line<'_>) -> Result<Self, ParseError> {
         use Operation::{
             And, Assignment, BitwiseWithOne, LShift, Not, Or, RShift, RegisterAssignment,
         };
 
-        if let Some(captures) = RE_ASSIGNMENT.captures(value) {
-            let value = captures.get(1).and_then(|s| s.as_str().parse().ok());
-
-            let target = captures
-                .get(2)
-                .ok_or_else(|| anyhow!("invalid assignment target"))?
-                .as_str();
-
-            if let Some(v) = value {
-                Ok(Self(Assignment(v), target.to_owned()))
+        let (operation, target) = line.split_once("->")?;
+        let wire = |part: &str| {
+            if part.chars().all(|c| c.is_ascii_lowercase()) {
+                Ok(part.to_owned())
             } else {
-                let register = captures
-                    .get(1)
-                    .ok_or_else(|| anyhow!("invalid assignment register"))?
-                    .as_str()
-                    .to_owned();
-                Ok(Self(RegisterAssignment(register), target.to_owned()))
+                Err(line.error_at(part, format!("invalid wire `{}`", part)))
+            }
+        };
+        let words: Vec<_> = operation.split_whitespace().collect();
+
+        let operation = match words.as_slice() {
+            [value] if value.chars().all(|c| c.is_ascii_digit()) => {
+                Assignment(line.parse_part(value)?)
             }
-        } else if let Some(captures) = RE_AND.captures(value) {
-            let a = captures
-                .get(1)
-                .ok_or_else(|| anyhow!("invalid AND first operand"))?
-                .as_str()
-                .to_owned();
-            let b = captures
-                .get(2)
-                .ok_or_else(|| anyhow!("invalid AND second operand"))?
-                .as_str()
-                .to_owned();
-            let target = captures
-                .get(3)
-                .ok_or_else(|| anyhow!("invalid AND target"))?
-                .as_str()
-                .to_owned();
-
-            Ok(Self(And(a, b), target))
-        } else if let Some(captures) = RE_OR.captures(value) {
-            let a = captures
-                .get(1)
-                .ok_or_else(|| anyhow!("invalid OR first operand"))?
-                .as_str()
-                .to_owned();
-            let b = captures
-                .get(2)
-                .ok_or_else(|| anyhow!("invalid OR second operand"))?
-                .as_str()
-                .to_owned();
-            let target = captures
-                .get(3)
-                .ok_or_else(|| anyhow!("invalid OR target"))?
-                .as_str()
-                .to_owned();
-
-            Ok(Self(Or(a, b), target))
-        } else if let Some(captures) = RE_LSHIFT.captures(value) {
-            let register = captures
-                .get(1)
-                .ok_or_else(|| anyhow!("invalid LSHIFT register"))?
-                .as_str()
-                .to_owned();
-            let shift_value = captures
-                .get(2)
-                .and_then(|s| s.as_str().parse().ok())
-                .ok_or_else(|| anyhow!("invalid LSHIFT value"))?;
-            let target = captures
-                .get(3)
-                .ok_or_else(|| anyhow!("invalid LSHIFT target"))?
-                .as_str()
-                .to_owned();
-
-            Ok(Self(LShift(register, shift_value), target))
-        } else if let Some(captures) = RE_RSHIFT.captures(value) {
-            let register = captures
-                .get(1)
-                .ok_or_else(|| anyhow!("invalid RSHIFT register"))?
-                .as_str()
-                .to_owned();
-            let shift_value = captures
-                .get(2)
-                .and_then(|s| s.as_str().parse().ok())
-                .ok_or_else(|| anyhow!("invalid RSHIFT value"))?;
-            let target = captures
-                .get(3)
-                .ok_or_else(|| anyhow!("invalid RSHIFT target"))?
-                .as_str()
-                .to_owned();
-
-            Ok(Self(RShift(register, shift_value), target))
-        } else if let Some(captures) = RE_NOT.captures(value) {
-            let register = captures
-                .get(1)
-                .ok_or_else(|| anyhow!("invalid NOT register"))?
-                .as_str()
-                .to_owned();
-            let target = captures
-                .get(2)
-                .ok_or_else(|| anyhow!("invalid NOT target"))?
-                .as_str()
-                .to_owned();
-
-            Ok(Self(Not(register), target))
-        } else if let Some(captures) = RE_COPY.captures(value) {
-            let register = captures
-                .get(1)
-                .ok_or_else(|| anyhow!("invalid COPY register"))?
-                .as_str()
-                .to_owned();
-            let target = captures
-                .get(2)
-                .ok_or_else(|| anyhow!("invalid COPY target"))?
-                .as_str()
-                .to_owned();
-
-            Ok(Self(BitwiseWithOne(register), target))
-        } else {
-            Err(anyhow!("unsupported value: {}", value))
+            [register] => RegisterAssignment(wire(register)?),
+            ["1", "AND", b] => BitwiseWithOne(wire(b)?),
+            [a, "AND", b] => And(wire(a)?, wire(b)?),
+            [a, "OR", b] => Or(wire(a)?, wire(b)?),
+            [a, "LSHIFT", shift] => LShift(wire(a)?, line.parse_part(shift)?),
+            [a, "RSHIFT", shift] => RShift(wire(a)?, line.parse_part(shift)?),
+            ["NOT", a] => Not(wire(a)?),
+            _ => return Err(line.error(1, format!("unknown operation `{}`", operation.trim()))),
+        };
+
+        match target.trim() {
+            "" => Err(line.error(line.text.chars().count() + 1, "missing target wire")),
+            target => Ok(Self(operation, wire(target)?)),
         }
     }
-}
 
-impl Instruction {
     fn execute(&self, state: &mut State) {
         match &self.0 {
             Operation::Assignment(v) => {
@@ -327,4 +221,24 @@ y AND y -> a";
     fn test_part2() {
         assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
     }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = |input| {
+            parse_lines(input, Instruction::parse)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            "line 2, column 1: unknown operation `x XOR y`",
+            err("123 -> x\nx XOR y -> z")
+        );
+        assert_eq!("line 1, column 8: invalid wire `X`", err("123 -> X"));
+        assert_eq!(
+            "line 1, column 10: can't parse `a`: invalid digit found in string",
+            err("x LSHIFT a -> b")
+        );
+        assert_eq!("line 1, column 6: expected `->`", err("NOT x"));
+    }
 }
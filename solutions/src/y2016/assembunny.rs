@@ -0,0 +1,288 @@
+use crate::utils::{
+    parse::{parse_lines, Line, ParseError},
+    vm::{Flow, Instruction, Machine, Operand, Registers},
+};
+
+/// Instructions of 2016 days 12, 23 and 25. Every parameter is an `Operand`, since
+/// `tgl` can turn valid instructions into invalid ones, which are then skipped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Assembunny {
+    Cpy(Operand, Operand),
+    Inc(Operand),
+    Dec(Operand),
+    Jnz(Operand, Operand),
+    Tgl(Operand),
+    Out(Operand),
+}
+
+impl Assembunny {
+    pub fn parse(line: Line<'_>) -> Result<Self, ParseError> {
+        use Assembunny::{Cpy, Dec, Inc, Jnz, Out, Tgl};
+
+        let mut parts = line.text.split_whitespace();
+        let mnemonic = parts.next().unwrap_or_default();
+        let operands = parts
+            .map(|p| {
+                p.parse::<Operand>()
+                    .map_err(|_| line.error_at(p, format!("invalid operand `{}`", p)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match (mnemonic, operands.as_slice()) {
+            ("cpy", &[x, y]) => Ok(Cpy(x, y)),
+            ("jnz", &[x, y]) => Ok(Jnz(x, y)),
+            ("inc", &[x]) => Ok(Inc(x)),
+            ("dec", &[x]) => Ok(Dec(x)),
+            ("tgl", &[x]) => Ok(Tgl(x)),
+            ("out", &[x]) => Ok(Out(x)),
+            ("cpy" | "jnz" | "inc" | "dec" | "tgl" | "out", _) => Err(line.error_at(
+                mnemonic,
+                format!("wrong number of operands for `{}`", mnemonic),
+            )),
+            _ => Err(line.error_at(mnemonic, format!("unknown instruction `{}`", mnemonic))),
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        use Assembunny::{Cpy, Dec, Inc, Jnz, Out, Tgl};
+
+        match self {
+            Inc(x) => Dec(x),
+            Dec(x) | Tgl(x) | Out(x) => Inc(x),
+            Jnz(x, y) => Cpy(x, y),
+            Cpy(x, y) => Jnz(x, y),
+        }
+    }
+}
+
+impl Instruction for Assembunny {
+    fn execute(&self, machine: &mut Machine<Self>) -> Flow {
+        use Assembunny::{Cpy, Dec, Inc, Jnz, Out, Tgl};
+
+        match *self {
+            Cpy(x, Operand::Register(r)) => machine.registers[r] = x.value(&machine.registers),
+            Inc(Operand::Register(r)) => machine.registers[r] += 1,
+            Dec(Operand::Register(r)) => machine.registers[r] -= 1,
+            Jnz(x, y) if x.value(&machine.registers) != 0 => {
+                return Flow::Jump(y.value(&machine.registers))
+            }
+            Tgl(x) => {
+                let target = machine.pc() as i64 + x.value(&machine.registers);
+
+                if target >= 0 {
+                    if let Some(&instruction) = machine.instruction(target as usize) {
+                        machine.set_instruction(target as usize, instruction.toggled());
+                    }
+                }
+            }
+            Out(x) => return Flow::Output(x.value(&machine.registers)),
+            // Invalid instructions, or `jnz` that doesn't jump.
+            _ => {}
+        }
+
+        Flow::Next
+    }
+}
+
+pub fn parse_program(input: &str) -> Vec<Assembunny> {
+    parse_lines(input, Assembunny::parse).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// A machine with the 4 registers `a` to `d`.
+pub fn machine(program: Vec<Assembunny>) -> Machine<Assembunny> {
+    Machine::new(program, 4)
+}
+
+/// Matches `inc a / dec b / jnz b -2`, with the first two in any order and maybe
+/// `dec a` instead. Returns `a`, the sign of its increment and `b`.
+fn match_add_loop(program: &[Assembunny], pc: usize) -> Option<(usize, i64, usize)> {
+    use Assembunny::{Dec, Inc, Jnz};
+    use Operand::{Register, Value};
+
+    let counter = match program.get(pc + 2)? {
+        Jnz(Register(c), Value(-2)) => *c,
+        _ => return None,
+    };
+
+    let other = match (program.get(pc)?, program.get(pc + 1)?) {
+        (other, Dec(Register(c))) | (Dec(Register(c)), other) if *c == counter => other,
+        _ => return None,
+    };
+
+    match *other {
+        Inc(Register(t)) if t != counter => Some((t, 1, counter)),
+        Dec(Register(t)) if t != counter => Some((t, -1, counter)),
+        _ => None,
+    }
+}
+
+/// `a += b; b = 0` instead of looping `b` times.
+fn add_loop(program: &[Assembunny], pc: usize, registers: &mut Registers) -> Option<usize> {
+    let (target, sign, counter) = match_add_loop(program, pc)?;
+
+    // Otherwise the loop would (almost) never end, keep the original behaviour.
+    if registers[counter] <= 0 {
+        return None;
+    }
+
+    registers[target] += sign * registers[counter];
+    registers[counter] = 0;
+
+    Some(pc + 3)
+}
+
+/// `cpy b c`, an add loop of `c` into `a`, then `dec d / jnz d -5`: `a += b * d`, and
+/// both `c` and `d` are cleared.
+fn multiply_loop(program: &[Assembunny], pc: usize, registers: &mut Registers) -> Option<usize> {
+    use Assembunny::{Cpy, Dec, Jnz};
+    use Operand::{Register, Value};
+
+    let (source, inner) = match program.get(pc)? {
+        Cpy(source, Register(inner)) => (*source, *inner),
+        _ => return None,
+    };
+
+    let (target, sign, counter) = match_add_loop(program, pc + 1)?;
+    let outer = match (program.get(pc + 4)?, program.get(pc + 5)?) {
+        (Dec(Register(d)), Jnz(Register(j), Value(-5))) if d == j => *d,
+        _ => return None,
+    };
+
+    let registers_used = [target, inner, outer];
+    if counter != inner
+        || outer == target
+        || outer == inner
+        || source
+            .register()
+            .is_some_and(|r| registers_used.contains(&r))
+    {
+        return None;
+    }
+
+    let (b, d) = (source.value(registers), registers[outer]);
+    if b <= 0 || d <= 0 {
+        return None;
+    }
+
+    registers[target] += sign * b * d;
+    registers[inner] = 0;
+    registers[outer] = 0;
+
+    Some(pc + 6)
+}
+
+/// Same as `machine`, with addition and multiplication loops replaced by arithmetic.
+/// Patterns are matched against the current program, so instructions modified by
+/// `tgl` are simply executed one by one.
+pub fn optimized_machine(program: Vec<Assembunny>) -> Machine<Assembunny> {
+    let mut m = machine(program);
+    m.add_peephole(Box::new(multiply_loop));
+    m.add_peephole(Box::new(add_loop));
+
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_errors() {
+        let err = |input| {
+            parse_lines(input, Assembunny::parse)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            "line 2, column 3: unknown instruction `cp`",
+            err("inc a\n  cp 1 a")
+        );
+        assert_eq!("line 1, column 5: invalid operand `1a`", err("inc 1a"));
+        assert_eq!(
+            "line 1, column 1: wrong number of operands for `jnz`",
+            err("jnz a")
+        );
+    }
+
+    #[test]
+    fn test_tgl() {
+        let program = parse_program(
+            "cpy 2 a
+            tgl a
+            tgl a
+            tgl a
+            cpy 1 a
+            dec a
+            dec a",
+        );
+        assert_eq!(7, program.len());
+
+        let mut m = machine(program);
+        m.run_to_end();
+
+        assert_eq!(3, m.registers[0]);
+        assert_eq!(
+            Assembunny::Jnz(Operand::Value(1), Operand::Register(0)),
+            m.program()[4]
+        );
+        assert_eq!(2, m.program_version());
+    }
+
+    #[test]
+    fn test_optimized_loops() {
+        let program = parse_program(
+            "cpy 6 b
+            cpy 7 d
+            cpy b c
+            inc a
+            dec c
+            jnz c -2
+            dec d
+            jnz d -5
+            cpy 5 c
+            dec c
+            dec a
+            jnz c -2",
+        );
+
+        let mut plain = machine(program.clone());
+        plain.run_to_end();
+        let mut optimized = optimized_machine(program);
+        optimized.run_to_end();
+
+        assert_eq!(Registers(vec![37, 6, 0, 0]), plain.registers);
+        assert_eq!(plain.registers, optimized.registers);
+        assert_eq!(5, optimized.steps());
+        assert!(plain.steps() > 100);
+    }
+
+    #[test]
+    fn test_optimization_fallback() {
+        // `tgl` turns `inc a` into `dec a` before the loop runs, unless the target is
+        // out of the program.
+        let source = |offset: i64| {
+            format!(
+                "cpy 5 a
+                cpy 3 b
+                cpy {offset} c
+                tgl c
+                inc a
+                dec b
+                jnz b -2"
+            )
+        };
+
+        for (offset, expected) in [(1, 2), (10, 8)] {
+            let program = parse_program(&source(offset));
+
+            let mut plain = machine(program.clone());
+            plain.run_to_end();
+            let mut optimized = optimized_machine(program);
+            optimized.run_to_end();
+
+            assert_eq!(expected, plain.registers[0]);
+            assert_eq!(expected, optimized.registers[0]);
+        }
+    }
+}
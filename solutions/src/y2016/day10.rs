@@ -5,6 +5,8 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 
+use crate::utils::parse::{parse_lines, Line, ParseError};
+
 pub struct Day10;
 
 crate::impl_day!("10", false);
@@ -15,27 +17,30 @@ enum Instruction {
     Give(i32, String, i32, String, i32),
 }
 
-fn process_input(input: &str) -> Vec<Instruction> {
-    input
-        .trim_end()
-        .lines()
-        .map(|l| {
-            let parsed = sscanf::sscanf!(l, "value {i32} goes to bot {i32}");
-            if let Ok((value, bot)) = parsed {
-                return Instruction::Value(value, bot);
-            }
+impl Instruction {
+    fn parse(line: Line<'_>) -> Result<Self, ParseError> {
+        let words: Vec<_> = line.text.split_whitespace().collect();
 
-            let parsed = sscanf::sscanf!(
-                l,
-                "bot {i32} gives low to {String} {i32} and high to {String} {i32}"
-            );
-            if let Ok((bot, low_dest, low, high_dest, high)) = parsed {
-                return Instruction::Give(bot, low_dest, low, high_dest, high);
+        match words.as_slice() {
+            ["value", value, "goes", "to", "bot", bot] => {
+                Ok(Self::Value(line.parse_part(value)?, line.parse_part(bot)?))
             }
+            ["bot", bot, "gives", "low", "to", low_dest, low, "and", "high", "to", high_dest, high] => {
+                Ok(Self::Give(
+                    line.parse_part(bot)?,
+                    low_dest.to_string(),
+                    line.parse_part(low)?,
+                    high_dest.to_string(),
+                    line.parse_part(high)?,
+                ))
+            }
+            _ => Err(line.error(1, "expected a `value` or a `bot` instruction")),
+        }
+    }
+}
 
-            panic!("invalid input: {}", l);
-        })
-        .collect_vec()
+fn process_input(input: &str) -> Vec<Instruction> {
+    parse_lines(input, Instruction::parse).unwrap_or_else(|e| panic!("{}", e))
 }
 
 #[derive(Debug, PartialEq)]
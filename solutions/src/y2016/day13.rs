@@ -1,9 +1,11 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 use colored::Colorize;
 use itertools::Itertools;
 use pathfinding::directed::dijkstra::dijkstra;
 
+use crate::utils::{grid::Grid, search::bfs_distances_within, viz};
+
 pub struct Day13;
 
 crate::impl_day!("13", true);
@@ -62,85 +64,44 @@ fn solve_p1_with_target(input: &str, target: (i32, i32)) -> Box<dyn std::fmt::Di
     Box::new(result.1)
 }
 
-fn recursively_visit_valid_neighbours(
-    visited_pos: &mut HashMap<(i32, i32), i32>,
-    current_pos: (i32, i32),
-    favorite_number: i32,
-    depth: i32,
-    max_depth: i32,
-) {
-    // This position was already handled by another call of the recursive function.
-    // Do not count it twice, stop the recursion here.
-    // if visited_pos.contains(current_pos) {
-    //     return;
-    // }
-
-    visited_pos.insert(current_pos, depth);
-
-    let valid_neighbours = compute_valid_successors(current_pos, favorite_number)
-        .into_iter()
-        .filter(|p| visited_pos.get(p).is_none_or(|v| depth < *v))
-        .collect_vec();
-
-    // println!(
-    //     "[{depth: >2}] Pos: {current_pos:?}, valid neighbours: {:?}",
-    //     valid_neighbours.clone().collect_vec()
-    // );
-
-    if depth < max_depth {
-        // Count current pos + other valid neighbours
-        for p in &valid_neighbours {
-            recursively_visit_valid_neighbours(
-                visited_pos,
-                *p,
-                favorite_number,
-                depth + 1,
-                max_depth,
-            );
-        }
+fn show_maze(favorite_number: i32, side_length: i32, visited_pos: &HashSet<(i32, i32)>) {
+    if !viz::enabled() {
+        return;
     }
-}
 
-#[allow(dead_code)]
-fn print_maze(favorite_number: i32, side_length: i32, visited_pos: &HashSet<(i32, i32)>) {
     let upper_digit = (side_length / 10 + 1) * 10;
+    let cells = (0..=upper_digit)
+        .flat_map(|y| (0..=upper_digit).map(move |x| (x, y)))
+        .collect_vec();
 
-    println!("   {}", (0..=(upper_digit / 10)).join("         "));
-    for y in 0..=upper_digit {
-        print!("{y: >2} ");
-        for x in 0..=upper_digit {
-            if is_wall(x, y, favorite_number) {
-                print!("{}", "#".dimmed());
-            } else if x == 1 && y == 1 {
-                print!("{}", "x".bold());
-            } else if visited_pos.contains(&(x, y)) {
-                print!("{}", "o".bold().green());
-            } else {
-                print!("{}", ".".dimmed());
-            }
-        }
-
-        println!();
-    }
+    viz::show(
+        format!("Reachable in {side_length} steps"),
+        &Grid::from_data(upper_digit as usize + 1, cells),
+        |pos| match pos {
+            Some(&(x, y)) if is_wall(x, y, favorite_number) => "#".dimmed(),
+            Some((1, 1)) => "x".bold(),
+            Some(pos) if visited_pos.contains(pos) => "o".bold().green(),
+            _ => ".".dimmed(),
+        },
+    );
 }
 
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
-    const MAX_DEPTH: i32 = 50;
+    const MAX_DEPTH: usize = 50;
 
-    let mut visited_pos: HashMap<(i32, i32), i32> = HashMap::new();
-    let current_pos = (1, 1);
     let favorite_number = process_input(input);
-    let depth = 0;
 
-    recursively_visit_valid_neighbours(
-        &mut visited_pos,
-        current_pos,
-        favorite_number,
-        depth,
+    let visited_pos = bfs_distances_within(
+        &(1, 1),
+        |p| compute_valid_successors(*p, favorite_number),
         MAX_DEPTH,
     );
 
-    // print_maze(favorite_number, MAX_DEPTH, &visited_pos);
+    show_maze(
+        favorite_number,
+        MAX_DEPTH as i32,
+        &visited_pos.keys().copied().collect(),
+    );
 
     Box::new(visited_pos.len())
 }
@@ -1,40 +1,40 @@
+use crate::y2016::assembunny::{optimized_machine, parse_program};
+
 pub struct Day23;
 
-crate::impl_day!("23", false);
+crate::impl_day!("23", true);
+
+fn run_with_eggs(input: &str, eggs: i64) -> i64 {
+    let mut m = optimized_machine(parse_program(input));
+    m.registers[0] = eggs;
+    m.run_to_end();
 
-fn process_input(input: &str) -> &str {
-    input
+    m.registers[0]
 }
 
-#[allow(unused)]
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
-
-    let res = "Part 1 not done";
-    Box::new(res)
+    Box::new(run_with_eggs(input, 7))
 }
 
-#[allow(unused)]
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
-
-    let res = "Part 2 not done";
-    Box::new(res)
+    Box::new(run_with_eggs(input, 12))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = r"";
+    const INPUT: &str = r"cpy 2 a
+tgl a
+tgl a
+tgl a
+cpy 1 a
+dec a
+dec a
+";
 
     #[test]
     fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
+        assert_eq!(3.to_string(), *solve_part1(INPUT).to_string());
     }
 }
@@ -1,40 +1,52 @@
+use crate::{
+    utils::vm::Status,
+    y2016::assembunny::{optimized_machine, parse_program, Assembunny},
+};
+
 pub struct Day25;
 
-crate::impl_day!("25", false);
+crate::impl_day!("25", true);
+
+/// Whether the program outputs `0, 1, 0, 1, ...` forever when `a` starts at `a`.
+fn is_clock_signal(program: &[Assembunny], a: i64) -> bool {
+    let mut m = optimized_machine(program.to_vec());
+    m.registers[0] = a;
+
+    // Most values are quickly ruled out by the first outputs.
+    for expected in [0, 1].iter().cycle().take(10) {
+        if m.run() != Status::Output(*expected) {
+            return false;
+        }
+    }
 
-fn process_input(input: &str) -> &str {
-    input
+    // The signal is infinite if the machine gets back to a previous state, and
+    // outputs a whole number of `0, 1` pairs in between.
+    match m.find_cycle() {
+        Some(cycle) => {
+            let repeated = cycle.outputs.len() - cycle.output_start;
+
+            repeated > 0
+                && repeated % 2 == 0
+                && cycle
+                    .outputs
+                    .iter()
+                    .enumerate()
+                    .all(|(i, &v)| v == i as i64 % 2)
+        }
+        None => false,
+    }
 }
 
-#[allow(unused)]
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    let program = parse_program(input);
+
+    let res = (1..).find(|&a| is_clock_signal(&program, a)).unwrap();
 
-    let res = "Part 1 not done";
     Box::new(res)
 }
 
 #[allow(unused)]
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
-
     let res = "Part 2 not done";
     Box::new(res)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const INPUT: &str = r"";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
-    }
-}
@@ -1,42 +1,57 @@
-use std::{convert::TryFrom, fmt::Display};
+use std::fmt::Display;
 
-use itertools::Itertools;
+use crate::utils::{
+    ocr::letters_or_picture,
+    parse::{parse_lines, Line, ParseError},
+    viz,
+};
 
 pub struct Day8;
 
 crate::impl_day!("8", true);
 
+#[derive(Debug)]
 enum Instruction {
     Rect(usize, usize),
     RotateRow(usize, usize),
     RotateColumn(usize, usize),
 }
 
-impl TryFrom<&str> for Instruction {
-    type Error = ();
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let parsed = sscanf::sscanf!(value, "rect {usize}x{usize}");
-        if let Ok((x, y)) = parsed {
-            return Ok(Self::Rect(x, y));
-        }
-
-        let parsed = sscanf::sscanf!(value, "rotate row y={usize} by {usize}");
-        if let Ok((x, y)) = parsed {
-            return Ok(Self::RotateRow(x, y));
-        }
-
-        let parsed = sscanf::sscanf!(value, "rotate column x={usize} by {usize}");
-        if let Ok((x, y)) = parsed {
-            return Ok(Self::RotateColumn(x, y));
+impl Instruction {
+    fn parse(line: Line<'_>) -> Result<Self, ParseError> {
+        let words: Vec<_> = line.text.split_whitespace().collect();
+        let coordinate = |part: &str, prefix: &str| {
+            part.strip_prefix(prefix)
+                .ok_or_else(|| line.error_at(part, format!("expected `{}`", prefix)))
+                .and_then(|n| line.parse_part(n))
+        };
+
+        match words.as_slice() {
+            ["rect", size] => {
+                let (x, y) = size.split_once('x').ok_or_else(|| {
+                    line.error_at(size, format!("expected `AxB`, got `{}`", size))
+                })?;
+
+                Ok(Self::Rect(line.parse_part(x)?, line.parse_part(y)?))
+            }
+            ["rotate", "row", y, "by", by] => {
+                Ok(Self::RotateRow(coordinate(y, "y=")?, line.parse_part(by)?))
+            }
+            ["rotate", "column", x, "by", by] => Ok(Self::RotateColumn(
+                coordinate(x, "x=")?,
+                line.parse_part(by)?,
+            )),
+            ["rotate", kind, ..] => {
+                Err(line.error_at(kind, format!("expected `row` or `column`, got `{}`", kind)))
+            }
+            [word, ..] => Err(line.error_at(word, format!("unknown instruction `{}`", word))),
+            [] => Err(line.error(1, "empty line")),
         }
-
-        Err(())
     }
 }
 
 fn process_input(input: &str) -> Vec<Instruction> {
-    input.lines().flat_map(Instruction::try_from).collect_vec()
+    parse_lines(input, Instruction::parse).unwrap_or_else(|e| panic!("{}", e))
 }
 
 struct Screen(Vec<Vec<bool>>);
@@ -105,26 +120,57 @@ fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
     let instructions = process_input(input);
     let mut screen = Screen(vec![vec![false; 50]; 6]);
 
-    instructions
-        .iter()
-        .for_each(|inst| screen.process_instruction(inst));
+    for inst in &instructions {
+        screen.process_instruction(inst);
+        viz::show(format!("{inst:?}"), screen.0.as_slice(), viz::pixels);
+    }
 
-    Box::new(screen)
+    Box::new(letters_or_picture(screen.to_string()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = r"";
+    const INPUT: &str = r"rect 4x1
+rotate column x=1 by 5
+rotate column x=2 by 5
+rotate column x=3 by 5
+rect 1x6";
 
     #[test]
     fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
+        assert_eq!(9.to_string(), *solve_part1(INPUT).to_string());
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
+        assert_eq!("L", solve_part2(INPUT).to_string());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = |input| {
+            parse_lines(input, Instruction::parse)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            "line 2, column 1: unknown instruction `rotat`",
+            err("rect 1x1\nrotat row y=0 by 1")
+        );
+        assert_eq!(
+            "line 1, column 6: expected `AxB`, got `3by2`",
+            err("rect 3by2")
+        );
+        assert_eq!(
+            "line 1, column 19: can't parse `-1`: invalid digit found in string",
+            err("rotate row y=0 by -1")
+        );
+        assert_eq!(
+            "line 1, column 15: expected `x=`",
+            err("rotate column y=1 by 2")
+        );
     }
 }
@@ -1 +1,3 @@
+pub mod assembunny;
+
 crate::days_gen!();
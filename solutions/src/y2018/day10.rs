@@ -1,40 +1,133 @@
+use itertools::Itertools;
+
+use crate::utils::{
+    detectors::{bounding_box_area, first_local_min},
+    grid::sparse::{Point, SparseGrid},
+    ocr::letters_or_picture,
+    parse::try_ints_n,
+    viz,
+};
+
 pub struct Day10;
 
-crate::impl_day!("10", false);
+crate::impl_day!("10", true);
+
+#[derive(Clone, Copy, Debug)]
+struct Star {
+    position: Point,
+    velocity: Point,
+}
 
-fn process_input(input: &str) -> &str {
+fn process_input(input: &str) -> Vec<Star> {
     input
+        .lines()
+        .filter_map(try_ints_n::<isize, 4>)
+        .map(|[x, y, vx, vy]| Star {
+            position: (x, y),
+            velocity: (vx, vy),
+        })
+        .collect_vec()
+}
+
+fn positions(stars: &[Star], t: isize) -> Vec<Point> {
+    stars
+        .iter()
+        .map(|s| {
+            (
+                s.position.0 + s.velocity.0 * t,
+                s.position.1 + s.velocity.1 * t,
+            )
+        })
+        .collect_vec()
+}
+
+/// The stars spell the message when they're the closest to each other, i.e. when
+/// their bounding box is the smallest.
+fn find_message(input: &str) -> (usize, Vec<Point>) {
+    let stars = process_input(input);
+
+    first_local_min((0..).map(|t| positions(&stars, t)), |p| {
+        bounding_box_area(p)
+    })
+    .unwrap()
 }
 
-#[allow(unused)]
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    let (seconds, message) = find_message(input);
+    let sky = message
+        .into_iter()
+        .map(|p| (p, true))
+        .collect::<SparseGrid<_>>();
 
-    let res = "Part 1 not done";
-    Box::new(res)
+    viz::show(format!("After {seconds} seconds"), &sky, viz::pixels);
+
+    let picture = viz::render(&sky, viz::pixels)
+        .lines()
+        .map(str::trim_end)
+        .join("\n");
+
+    Box::new(letters_or_picture(format!("\n{picture}")))
 }
 
-#[allow(unused)]
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
-
-    let res = "Part 2 not done";
-    Box::new(res)
+    Box::new(find_message(input).0)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = r"";
+    const INPUT: &str = r"position=< 9,  1> velocity=< 0,  2>
+position=< 7,  0> velocity=<-1,  0>
+position=< 3, -2> velocity=<-1,  1>
+position=< 6, 10> velocity=<-2, -1>
+position=< 2, -4> velocity=< 2,  2>
+position=<-6, 10> velocity=< 2, -2>
+position=< 1,  8> velocity=< 1, -1>
+position=< 1,  7> velocity=< 1,  0>
+position=<-3, 11> velocity=< 1, -2>
+position=< 7,  6> velocity=<-1, -1>
+position=<-2,  3> velocity=< 1,  0>
+position=<-4,  3> velocity=< 2,  0>
+position=<10, -3> velocity=<-1,  1>
+position=< 5, 11> velocity=< 1, -2>
+position=< 4,  7> velocity=< 0, -1>
+position=< 8, -2> velocity=< 0,  1>
+position=<15,  0> velocity=<-2,  0>
+position=< 1,  6> velocity=< 1,  0>
+position=< 8,  9> velocity=< 0, -1>
+position=< 3,  3> velocity=<-1,  1>
+position=< 0,  5> velocity=< 0, -1>
+position=<-2,  2> velocity=< 2,  0>
+position=< 5, -2> velocity=< 1,  2>
+position=< 1,  4> velocity=< 2,  1>
+position=<-2,  7> velocity=< 2, -2>
+position=< 3,  6> velocity=<-1, -1>
+position=< 5,  0> velocity=< 1,  0>
+position=<-6,  0> velocity=< 2,  0>
+position=< 5,  9> velocity=< 1, -2>
+position=<14,  7> velocity=<-2,  0>
+position=<-3,  6> velocity=< 2, -1>";
 
     #[test]
     fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
+        // The example is 8 pixels high, no font has letters of that size.
+        assert_eq!(
+            "
+█   █  ███
+█   █   █
+█   █   █
+█████   █
+█   █   █
+█   █   █
+█   █   █
+█   █  ███",
+            solve_part1(INPUT).to_string()
+        );
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
+        assert_eq!(3.to_string(), *solve_part2(INPUT).to_string());
     }
 }
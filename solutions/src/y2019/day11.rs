@@ -2,6 +2,7 @@ pub struct Day11;
 
 crate::impl_day!("11", true);
 
+use crate::utils::ocr::letters_or_picture;
 use crate::y2019::intcode_computer::IntcodeComputer;
 use std::collections::HashMap;
 
@@ -192,5 +193,5 @@ fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
 
     let mut r = Robot::from(&input, 1);
     r.paint_hull();
-    Box::new(r.display_hull())
+    Box::new(letters_or_picture(r.display_hull()))
 }
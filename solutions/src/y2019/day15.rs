@@ -1,38 +1,67 @@
 pub struct Day15;
 
-crate::impl_day!("15", false);
+crate::impl_day!("15", true);
 
-fn process_input(input: &str) -> &str {
-    input
+use crate::y2019::{
+    intcode_computer::{IntcodeComputer, State},
+    intcode_search::{bfs, bfs_distances},
+};
+
+type Pos = (isize, isize);
+
+/// Movement commands, in the order expected by the droid: north, south, west, east.
+const MOVES: [(i64, (isize, isize)); 4] = [(1, (0, -1)), (2, (0, 1)), (3, (-1, 0)), (4, (1, 0))];
+
+fn process_input(input: &str) -> Vec<i64> {
+    input.trim().split(',').flat_map(str::parse).collect()
 }
 
-#[allow(unused)]
-fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+/// Moves the droid, returns its new position and whether it found the oxygen system.
+fn move_droid(
+    &(pos, _): &(Pos, bool),
+    &(command, (dx, dy)): &(i64, (isize, isize)),
+    droid: &mut IntcodeComputer,
+) -> Option<(Pos, bool)> {
+    droid.provide_input(vec![command]);
 
-    Box::new("Part 1 not done")
+    match droid.process() {
+        State::GaveOutput(0) => None,
+        State::GaveOutput(status) => Some(((pos.0 + dx, pos.1 + dy), status == 2)),
+        s => panic!("Unexpected droid state: {:?}", s),
+    }
 }
 
-#[allow(unused)]
-fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+fn find_oxygen_system(program: &[i64]) -> ((Pos, bool), usize, IntcodeComputer) {
+    let droid = IntcodeComputer::from(program, vec![]);
 
-    Box::new("Part 2 not done")
+    bfs(
+        ((0, 0), false),
+        droid,
+        |_| MOVES,
+        move_droid,
+        |&(_, oxygen)| oxygen,
+    )
+    .expect("Oxygen system not found")
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
+    let input = process_input(input);
 
-    const INPUT: &str = r"";
+    let (_, distance, _) = find_oxygen_system(&input);
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
-    }
+    Box::new(distance)
+}
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
-    }
+fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
+    let input = process_input(input);
+
+    // Oxygen spreads from the system the same way the droid explores the area.
+    let (start, _, droid) = find_oxygen_system(&input);
+    let res = bfs_distances(start, droid, |_| MOVES, move_droid)
+        .values()
+        .max()
+        .copied()
+        .unwrap();
+
+    Box::new(res)
 }
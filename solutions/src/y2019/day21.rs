@@ -1,38 +1,49 @@
 pub struct Day21;
 
-crate::impl_day!("21", false);
+crate::impl_day!("21", true);
 
-fn process_input(input: &str) -> &str {
-    input
+use crate::y2019::intcode_ascii::AsciiComputer;
+
+fn process_input(input: &str) -> Vec<i64> {
+    input.trim().split(',').flat_map(str::parse).collect()
 }
 
-#[allow(unused)]
-fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+fn run_springscript(program: &[i64], script: &[&str]) -> i64 {
+    let mut c = AsciiComputer::new(program);
+    let output = c.execute(script.iter().copied());
 
-    Box::new("Part 1 not done")
+    // The droid falls into space: the output shows where.
+    match output.answers.last() {
+        Some(&damage) => damage,
+        None => panic!("{}", output.text),
+    }
 }
 
-#[allow(unused)]
-fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
+fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
     let input = process_input(input);
 
-    Box::new("Part 2 not done")
-}
+    // Jump if there's a hole in the next three tiles, and ground to land on.
+    let res = run_springscript(
+        &input,
+        &[
+            "NOT A J", "NOT B T", "OR T J", "NOT C T", "OR T J", "AND D J", "WALK",
+        ],
+    );
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    Box::new(res)
+}
 
-    const INPUT: &str = r"";
+fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
+    let input = process_input(input);
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
-    }
+    // Same, but only if we can either walk or jump again after landing.
+    let res = run_springscript(
+        &input,
+        &[
+            "NOT A J", "NOT B T", "OR T J", "NOT C T", "OR T J", "AND D J", "NOT E T", "NOT T T",
+            "OR H T", "AND T J", "RUN",
+        ],
+    );
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
-    }
+    Box::new(res)
 }
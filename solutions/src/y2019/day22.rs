@@ -1,38 +1,83 @@
+use crate::utils::math::Affine;
+
 pub struct Day22;
 
-crate::impl_day!("22", false);
+crate::impl_day!("22", true);
 
-fn process_input(input: &str) -> &str {
+/// The whole shuffle, as the map from the position of a card before it to its position
+/// after it.
+fn process_input(input: &str, deck_size: i128) -> Affine<i128> {
     input
+        .trim()
+        .lines()
+        .map(|l| {
+            if l == "deal into new stack" {
+                Affine::new(-1, -1, deck_size)
+            } else if let Ok(n) = sscanf::sscanf!(l, "cut {i128}") {
+                Affine::new(1, -n, deck_size)
+            } else if let Ok(n) = sscanf::sscanf!(l, "deal with increment {i128}") {
+                Affine::new(n, 0, deck_size)
+            } else {
+                panic!("Unknown technique: {}", l)
+            }
+        })
+        .fold(Affine::identity(deck_size), |acc, t| acc.then(&t))
 }
 
-#[allow(unused)]
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    let shuffle = process_input(input, 10_007);
 
-    Box::new("Part 1 not done")
+    Box::new(shuffle.apply(2019))
 }
 
-#[allow(unused)]
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    const DECK_SIZE: i128 = 119_315_717_514_047;
+    const REPETITIONS: i128 = 101_741_582_076_661;
+
+    let shuffle = process_input(input, DECK_SIZE).pow(REPETITIONS);
+    let res = shuffle.inverse().unwrap().apply(2020);
 
-    Box::new("Part 2 not done")
+    Box::new(res)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = r"";
+    fn deck(input: &str) -> Vec<i128> {
+        let inverse = process_input(input, 10).inverse().unwrap();
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
+        (0..10).map(|position| inverse.apply(position)).collect()
     }
 
     #[test]
-    fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
+    fn test_shuffles() {
+        assert_eq!(
+            vec![0, 3, 6, 9, 2, 5, 8, 1, 4, 7],
+            deck("deal with increment 7\ndeal into new stack\ndeal into new stack")
+        );
+        assert_eq!(
+            vec![3, 0, 7, 4, 1, 8, 5, 2, 9, 6],
+            deck("cut 6\ndeal with increment 7\ndeal into new stack")
+        );
+        assert_eq!(
+            vec![6, 3, 0, 7, 4, 1, 8, 5, 2, 9],
+            deck("deal with increment 7\ndeal with increment 9\ncut -2")
+        );
+        assert_eq!(
+            vec![9, 2, 5, 8, 1, 4, 7, 0, 3, 6],
+            deck(
+                "deal into new stack
+cut -2
+deal with increment 7
+cut 8
+cut -4
+deal with increment 7
+cut 3
+deal with increment 9
+deal with increment 3
+cut -1"
+            )
+        );
     }
 }
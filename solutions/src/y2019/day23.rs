@@ -1,38 +1,60 @@
 pub struct Day23;
 
-crate::impl_day!("23", false);
+crate::impl_day!("23", true);
 
-fn process_input(input: &str) -> &str {
-    input
+use crate::y2019::intcode_cluster::{Cluster, Event, Routing};
+
+const NAT: usize = 255;
+
+fn process_input(input: &str) -> Vec<i64> {
+    input.trim().split(',').flat_map(str::parse).collect()
+}
+
+fn boot_network(program: &[i64]) -> Cluster {
+    let mut cluster = Cluster::new(program, 50, Routing::Packets { size: 3 }).with_idle_input(-1);
+
+    for address in 0..cluster.len() {
+        cluster.send(address, &[address as i64]);
+    }
+
+    cluster
 }
 
-#[allow(unused)]
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
     let input = process_input(input);
 
-    Box::new("Part 1 not done")
+    let res = boot_network(&input).run_until(|_, event| match event {
+        Event::Message(m) if m.to == NAT => Some(m.values[1]),
+        _ => None,
+    });
+
+    Box::new(res.unwrap())
 }
 
-#[allow(unused)]
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
     let input = process_input(input);
 
-    Box::new("Part 2 not done")
-}
+    let mut nat_packet: Option<Vec<i64>> = None;
+    let mut last_y = None;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    let res = boot_network(&input).run_until(|cluster, event| {
+        match event {
+            Event::Message(m) if m.to == NAT => nat_packet = Some(m.values.clone()),
+            Event::Idle => {
+                let packet = nat_packet.as_ref()?;
 
-    const INPUT: &str = r"";
+                if last_y == Some(packet[1]) {
+                    return last_y;
+                }
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
-    }
+                last_y = Some(packet[1]);
+                cluster.send(0, packet);
+            }
+            _ => {}
+        }
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
-    }
+        None
+    });
+
+    Box::new(res.unwrap())
 }
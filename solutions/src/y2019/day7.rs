@@ -1,38 +1,82 @@
 pub struct Day7;
 
-crate::impl_day!("7", false);
+crate::impl_day!("7", true);
 
-fn process_input(input: &str) -> &str {
-    input
+use itertools::Itertools;
+
+use crate::y2019::intcode_cluster::{Cluster, Event, Routing};
+
+fn process_input(input: &str) -> Vec<i64> {
+    input.trim().split(',').flat_map(str::parse).collect()
+}
+
+fn run_amplifiers(program: &[i64], phases: &[i64], routing: Routing) -> i64 {
+    let mut cluster = Cluster::new(program, phases.len(), routing);
+
+    for (address, &phase) in phases.iter().enumerate() {
+        cluster.send(address, &[phase]);
+    }
+    cluster.send(0, &[0]);
+
+    // The thrusters get the last signal sent by the last amplifier.
+    let mut signal = 0;
+    cluster.run_until(|_, event| -> Option<()> {
+        if let Event::Message(m) = event {
+            if m.from == phases.len() - 1 {
+                signal = m.values[0];
+            }
+        }
+
+        None
+    });
+
+    signal
+}
+
+fn max_signal(program: &[i64], phases: std::ops::Range<i64>, routing: Routing) -> i64 {
+    phases
+        .permutations(5)
+        .map(|p| run_amplifiers(program, &p, routing.clone()))
+        .max()
+        .unwrap()
 }
 
-#[allow(unused)]
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
     let input = process_input(input);
 
-    Box::new("Part 1 not done")
+    Box::new(max_signal(&input, 0..5, Routing::chain(5)))
 }
 
-#[allow(unused)]
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
     let input = process_input(input);
 
-    Box::new("Part 2 not done")
+    Box::new(max_signal(&input, 5..10, Routing::ring(5)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = r"";
-
     #[test]
     fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
+        assert_eq!(
+            43210.to_string(),
+            *solve_part1("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0").to_string()
+        );
+        assert_eq!(
+            65210.to_string(),
+            *solve_part1(
+                "3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,1002,33,7,33,1,33,31,31,1,32,31,31,4,31,99,0,0,0"
+            )
+            .to_string()
+        );
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
+        assert_eq!(
+            139629729.to_string(),
+            *solve_part2("3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5").to_string()
+        );
     }
 }
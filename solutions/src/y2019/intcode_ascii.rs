@@ -0,0 +1,165 @@
+#![allow(unused)]
+
+use std::io::{self, BufRead, Write};
+
+use crate::y2019::intcode_computer::{IntcodeComputer, State};
+
+/// Everything output by the machine until it waited for input or halted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AsciiOutput {
+    pub text: String,
+    /// Values that aren't ASCII characters, usually the answer of the puzzle.
+    pub answers: Vec<i64>,
+    pub halted: bool,
+}
+
+/// Talks to an Intcode program with lines of text.
+pub struct AsciiComputer {
+    pub computer: IntcodeComputer,
+}
+
+impl AsciiComputer {
+    pub fn new(program: &[i64]) -> Self {
+        Self {
+            computer: IntcodeComputer::from(program, vec![]),
+        }
+    }
+
+    /// Queues a line of input, a newline is added at the end.
+    pub fn send_line(&mut self, line: &str) {
+        let mut input: Vec<i64> = line.bytes().map(i64::from).collect();
+        input.push(i64::from(b'\n'));
+
+        self.computer.provide_input(input);
+    }
+
+    pub fn send_lines<'a, I>(&mut self, lines: I)
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        for line in lines {
+            self.send_line(line);
+        }
+    }
+
+    /// Runs the machine until it halts, or waits for input that wasn't sent yet.
+    pub fn run(&mut self) -> AsciiOutput {
+        let mut output = AsciiOutput::default();
+
+        loop {
+            match self.computer.process() {
+                State::Runnable => {}
+                State::WaitingForInput => break,
+                State::Halted => {
+                    output.halted = true;
+                    break;
+                }
+                State::GaveOutput(v) if (0..128).contains(&v) => output.text.push(v as u8 as char),
+                State::GaveOutput(v) => output.answers.push(v),
+            }
+        }
+
+        output
+    }
+
+    /// Sends the lines, then runs the machine.
+    pub fn execute<'a, I>(&mut self, lines: I) -> AsciiOutput
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        self.send_lines(lines);
+        self.run()
+    }
+
+    /// Prints the output of the machine and forwards every line read, until the
+    /// machine halts or the input ends.
+    pub fn repl<R, W>(&mut self, input: R, mut out: W) -> io::Result<()>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        let mut lines = input.lines();
+
+        loop {
+            let output = self.run();
+
+            write!(out, "{}", output.text)?;
+            for answer in output.answers {
+                writeln!(out, "{answer}")?;
+            }
+            out.flush()?;
+
+            if output.halted {
+                return Ok(());
+            }
+
+            match lines.next() {
+                Some(line) => self.send_line(line?.trim_end()),
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// Plays with the program from the terminal, e.g. the text adventure of day 25.
+    /// This is what `single -y 2019 -d 25 --interactive` runs.
+    pub fn interactive(&mut self) -> io::Result<()> {
+        self.repl(io::stdin().lock(), io::stdout())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2019::intcode_assembly::assemble;
+
+    /// Prints a prompt, echoes a line, then outputs its length plus 1000.
+    const ECHO: &str = "
+                OUT #62
+                OUT #10
+        loop:   IN @c
+                EQ @c, #10, @tmp
+                JT @tmp, #done
+                ADD @n, #1, @n
+                OUT @c
+                JT #1, #loop
+        done:   OUT #10
+                ADD @n, #1000, @n
+                OUT @n
+                HALT
+        c:      DATA 0
+        tmp:    DATA 0
+        n:      DATA 0
+    ";
+
+    #[test]
+    fn test_run() {
+        let mut c = AsciiComputer::new(&assemble(ECHO).unwrap());
+
+        assert_eq!(
+            AsciiOutput {
+                text: ">\n".to_string(),
+                answers: vec![],
+                halted: false,
+            },
+            c.run()
+        );
+        assert_eq!(
+            AsciiOutput {
+                text: "abc\n".to_string(),
+                answers: vec![1003],
+                halted: true,
+            },
+            c.execute(vec!["abc"])
+        );
+    }
+
+    #[test]
+    fn test_repl() {
+        let mut c = AsciiComputer::new(&assemble(ECHO).unwrap());
+        let mut out = vec![];
+
+        c.repl("hello\nignored\n".as_bytes(), &mut out).unwrap();
+
+        assert_eq!(">\nhello\n1005\n", String::from_utf8(out).unwrap());
+    }
+}
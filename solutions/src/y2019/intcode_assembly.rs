@@ -0,0 +1,446 @@
+#![allow(unused)]
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use crate::y2019::intcode_computer::{Opcode, ParameterMode};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub address: usize,
+    pub opcode: Opcode,
+    pub parameters: Vec<(ParameterMode, i64)>,
+}
+
+impl Instruction {
+    /// Decodes the instruction at `address`, if the value there is a valid
+    /// instruction that fits in `memory`.
+    pub fn decode(memory: &[i64], address: usize) -> Option<Self> {
+        let value = *memory.get(address)?;
+        if value < 0 {
+            return None;
+        }
+
+        let opcode = Opcode::from_instruction(value)?;
+        let count = opcode.parameter_count();
+        let mut modes = value / 100;
+        let mut parameters = Vec::with_capacity(count);
+
+        for i in 0..count {
+            let mode = ParameterMode::from_digit(modes % 10)?;
+            if mode == ParameterMode::Immediate && opcode.writes_parameter(i) {
+                return None;
+            }
+
+            parameters.push((mode, *memory.get(address + 1 + i)?));
+            modes /= 10;
+        }
+
+        // Leftover digits would be lost when encoding the instruction back.
+        if modes != 0 {
+            return None;
+        }
+
+        Some(Self {
+            address,
+            opcode,
+            parameters,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.parameters.len() + 1
+    }
+
+    pub fn encode(&self) -> Vec<i64> {
+        let modes = self
+            .parameters
+            .iter()
+            .rev()
+            .fold(0, |acc, (mode, _)| acc * 10 + *mode as i64);
+
+        let mut encoded = vec![self.opcode as i64 + modes * 100];
+        encoded.extend(self.parameters.iter().map(|(_, value)| value));
+
+        encoded
+    }
+
+    /// Immediate target of a `JT`/`JF` instruction.
+    pub fn jump_target(&self) -> Option<usize> {
+        match (self.opcode, self.parameters.get(1)) {
+            (Opcode::JumpIfTrue | Opcode::JumpIfFalse, Some(&(ParameterMode::Immediate, t)))
+                if t >= 0 =>
+            {
+                Some(t as usize)
+            }
+            _ => None,
+        }
+    }
+
+    fn format_with_labels(&self, labels: &HashMap<usize, String>) -> String {
+        let parameters = self
+            .parameters
+            .iter()
+            .enumerate()
+            .map(|(i, &(mode, value))| match mode {
+                ParameterMode::Immediate if i == 1 && self.jump_target().is_some() => labels
+                    .get(&(value as usize))
+                    .map_or_else(|| format!("#{value}"), |label| format!("#{label}")),
+                _ => format_parameter(mode, value),
+            })
+            .collect::<Vec<_>>();
+
+        if parameters.is_empty() {
+            self.opcode.mnemonic().to_string()
+        } else {
+            format!("{} {}", self.opcode.mnemonic(), parameters.join(", "))
+        }
+    }
+}
+
+fn format_parameter(mode: ParameterMode, value: i64) -> String {
+    match mode {
+        ParameterMode::Position => format!("@{value}"),
+        ParameterMode::Immediate => format!("#{value}"),
+        ParameterMode::Relative if value == 0 => "rel".to_string(),
+        ParameterMode::Relative => format!("rel{value:+}"),
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.format_with_labels(&HashMap::new()))
+    }
+}
+
+enum Line {
+    Code(Instruction),
+    Data(i64),
+}
+
+/// Linear sweep of the program: anything that doesn't decode as an instruction is
+/// treated as data, one cell at a time.
+fn sweep(program: &[i64]) -> Vec<Line> {
+    let mut lines = vec![];
+    let mut address = 0;
+
+    while address < program.len() {
+        if let Some(instruction) = Instruction::decode(program, address) {
+            address += instruction.size();
+            lines.push(Line::Code(instruction));
+        } else {
+            lines.push(Line::Data(program[address]));
+            address += 1;
+        }
+    }
+
+    lines
+}
+
+/// Renders a program as one mnemonic per line, with its address as a comment.
+/// Immediate jump targets landing on an instruction get a label. The output can be
+/// fed back to `assemble`.
+pub fn disassemble(program: &[i64]) -> String {
+    let lines = sweep(program);
+
+    let mut starts = HashSet::new();
+    let mut address = 0;
+    for line in &lines {
+        starts.insert(address);
+        address += match line {
+            Line::Code(instruction) => instruction.size(),
+            Line::Data(_) => 1,
+        };
+    }
+
+    let labels: HashMap<usize, String> = lines
+        .iter()
+        .filter_map(|line| match line {
+            Line::Code(instruction) => instruction.jump_target(),
+            Line::Data(_) => None,
+        })
+        .filter(|target| starts.contains(target))
+        .map(|target| (target, format!("L{target}")))
+        .collect();
+
+    let mut out = String::new();
+    let mut address = 0;
+
+    for line in &lines {
+        let (text, len) = match line {
+            Line::Code(instruction) => {
+                (instruction.format_with_labels(&labels), instruction.size())
+            }
+            Line::Data(value) => (format!("DATA {value}"), 1),
+        };
+
+        let label = labels
+            .get(&address)
+            .map_or_else(String::new, |l| format!("{l}:"));
+
+        out.push_str(&format!("{label:<8}{text:<32}; {address}\n"));
+        address += len;
+    }
+
+    out
+}
+
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum AssemblyError {
+    #[error("line {0}: unknown mnemonic `{1}`")]
+    UnknownMnemonic(usize, String),
+    #[error("line {line}: {mnemonic} takes {expected} parameters, got {found}")]
+    WrongParameterCount {
+        line: usize,
+        mnemonic: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    #[error("line {0}: invalid parameter `{1}`")]
+    InvalidParameter(usize, String),
+    #[error("line {0}: immediate mode can't be used for a written parameter")]
+    ImmediateWrite(usize),
+    #[error("line {0}: unknown label `{1}`")]
+    UnknownLabel(usize, String),
+    #[error("line {0}: label `{1}` is defined twice")]
+    DuplicateLabel(usize, String),
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn resolve(
+    value: &str,
+    line: usize,
+    labels: &HashMap<String, usize>,
+) -> Result<i64, AssemblyError> {
+    if let Ok(v) = value.parse() {
+        Ok(v)
+    } else if is_identifier(value) {
+        labels
+            .get(value)
+            .map(|&address| address as i64)
+            .ok_or_else(|| AssemblyError::UnknownLabel(line, value.to_string()))
+    } else {
+        Err(AssemblyError::InvalidParameter(line, value.to_string()))
+    }
+}
+
+fn parse_parameter(
+    parameter: &str,
+    line: usize,
+    labels: &HashMap<String, usize>,
+) -> Result<(ParameterMode, i64), AssemblyError> {
+    if let Some(rest) = parameter.strip_prefix('@') {
+        Ok((ParameterMode::Position, resolve(rest, line, labels)?))
+    } else if let Some(rest) = parameter.strip_prefix('#') {
+        Ok((ParameterMode::Immediate, resolve(rest, line, labels)?))
+    } else if let Some(rest) = parameter.strip_prefix("rel") {
+        let offset = match rest.strip_prefix('+') {
+            _ if rest.is_empty() => 0,
+            Some(offset) => resolve(offset, line, labels)?,
+            None => resolve(rest, line, labels)?,
+        };
+
+        Ok((ParameterMode::Relative, offset))
+    } else {
+        Err(AssemblyError::InvalidParameter(line, parameter.to_string()))
+    }
+}
+
+struct SourceLine<'a> {
+    number: usize,
+    mnemonic: &'a str,
+    parameters: Vec<&'a str>,
+}
+
+/// Assembles the syntax produced by `disassemble`:
+///
+/// ```text
+/// start:  IN @value       ; comments are ignored
+///         ADD @value, #-1, rel+2
+///         JT @value, #start
+///         HALT
+/// value:  DATA 0, 1, 2
+/// ```
+pub fn assemble(source: &str) -> Result<Vec<i64>, AssemblyError> {
+    let mut labels: HashMap<String, usize> = HashMap::new();
+    let mut lines = vec![];
+    let mut address = 0;
+
+    // First pass: compute the address of every label.
+    for (i, raw) in source.lines().enumerate() {
+        let number = i + 1;
+        let mut code = raw.split(';').next().unwrap().trim();
+
+        if let Some((label, rest)) = code.split_once(':') {
+            let label = label.trim();
+            if !is_identifier(label) {
+                return Err(AssemblyError::InvalidParameter(number, label.to_string()));
+            }
+            if labels.insert(label.to_string(), address).is_some() {
+                return Err(AssemblyError::DuplicateLabel(number, label.to_string()));
+            }
+
+            code = rest.trim();
+        }
+
+        if code.is_empty() {
+            continue;
+        }
+
+        let (mnemonic, rest) = code.split_once(char::is_whitespace).unwrap_or((code, ""));
+        let parameters = rest
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|p| !p.is_empty())
+            .collect::<Vec<_>>();
+
+        address += if mnemonic.eq_ignore_ascii_case("DATA") {
+            parameters.len()
+        } else {
+            let opcode = Opcode::from_mnemonic(mnemonic)
+                .ok_or_else(|| AssemblyError::UnknownMnemonic(number, mnemonic.to_string()))?;
+
+            if parameters.len() != opcode.parameter_count() {
+                return Err(AssemblyError::WrongParameterCount {
+                    line: number,
+                    mnemonic: opcode.mnemonic(),
+                    expected: opcode.parameter_count(),
+                    found: parameters.len(),
+                });
+            }
+
+            opcode.parameter_count() + 1
+        };
+
+        lines.push(SourceLine {
+            number,
+            mnemonic,
+            parameters,
+        });
+    }
+
+    // Second pass: encode, now that every label is known.
+    let mut program = Vec::with_capacity(address);
+
+    for SourceLine {
+        number,
+        mnemonic,
+        parameters,
+    } in lines
+    {
+        if mnemonic.eq_ignore_ascii_case("DATA") {
+            for p in parameters {
+                program.push(resolve(p, number, &labels)?);
+            }
+
+            continue;
+        }
+
+        let opcode = Opcode::from_mnemonic(mnemonic).unwrap();
+        let parameters = parameters
+            .iter()
+            .map(|p| parse_parameter(p, number, &labels))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if parameters
+            .iter()
+            .enumerate()
+            .any(|(i, (mode, _))| *mode == ParameterMode::Immediate && opcode.writes_parameter(i))
+        {
+            return Err(AssemblyError::ImmediateWrite(number));
+        }
+
+        let instruction = Instruction {
+            address: program.len(),
+            opcode,
+            parameters,
+        };
+        program.extend(instruction.encode());
+    }
+
+    Ok(program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2019::intcode_computer::{IntcodeComputer, State};
+
+    const TEST_VEC_1: &[i64] = &[
+        3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+        1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20,
+        1105, 1, 46, 98, 99,
+    ];
+
+    #[test]
+    fn test_disassemble() {
+        let text = disassemble(&[1002, 4, 3, 4, 1105, 1, 0, 204, -2, 99, 7]);
+        let lines = text.lines().map(str::trim_end).collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                "L0:     MUL @4, #3, @4                  ; 0",
+                "        JT #1, #L0                      ; 4",
+                "        OUT rel-2                       ; 7",
+                "        HALT                            ; 9",
+                "        DATA 7                          ; 10",
+            ],
+            lines
+        );
+    }
+
+    #[test]
+    fn test_assemble() {
+        let program = assemble(
+            "
+            ; Outputs 1 if the input is 8, 0 otherwise.
+            IN @input
+            EQ @input, @eight, @input
+            OUT @input
+            HALT
+     input: DATA -1
+     eight: DATA 8",
+        )
+        .unwrap();
+
+        assert_eq!(vec![3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8], program);
+        assert_eq!(
+            State::GaveOutput(1),
+            IntcodeComputer::process_from(&program, vec![8])
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_eq!(
+            TEST_VEC_1,
+            assemble(&disassemble(TEST_VEC_1)).unwrap().as_slice()
+        );
+    }
+
+    #[test]
+    fn test_assembly_errors() {
+        assert_eq!(
+            Err(AssemblyError::UnknownMnemonic(1, "NOP".to_string())),
+            assemble("NOP")
+        );
+        assert_eq!(Err(AssemblyError::ImmediateWrite(1)), assemble("IN #3"));
+        assert_eq!(
+            Err(AssemblyError::UnknownLabel(2, "nowhere".to_string())),
+            assemble("HALT\nJT #1, #nowhere")
+        );
+        assert!(matches!(
+            assemble("ADD @1, @2"),
+            Err(AssemblyError::WrongParameterCount { expected: 3, .. })
+        ));
+    }
+}
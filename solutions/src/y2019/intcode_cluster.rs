@@ -0,0 +1,313 @@
+#![allow(unused)]
+
+use std::convert::TryFrom;
+
+use crate::y2019::intcode_computer::{IntcodeComputer, State};
+
+pub type Address = usize;
+
+/// How the outputs of the machines are turned into messages. A message sent to an
+/// address that isn't one of the machines leaves the cluster: it's only seen by the
+/// hook of `run_until`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Routing {
+    /// Every value output by machine `i` is sent to `destinations[i]`.
+    Table(Vec<Address>),
+    /// Outputs are packets of `size` values: the destination, then the payload.
+    Packets { size: usize },
+}
+
+impl Routing {
+    /// Machine `i` sends to `i + 1`, and the last one sends out of the cluster.
+    pub fn chain(len: usize) -> Self {
+        Self::Table((1..=len).collect())
+    }
+
+    /// Machine `i` sends to `i + 1`, and the last one sends to the first one.
+    pub fn ring(len: usize) -> Self {
+        Self::Table((1..=len).map(|i| i % len).collect())
+    }
+
+    fn message_size(&self) -> usize {
+        match self {
+            Self::Table(_) => 1,
+            Self::Packets { size } => *size,
+        }
+    }
+
+    fn route(&self, from: Address, mut values: Vec<i64>) -> Message {
+        let to = match self {
+            Self::Table(destinations) => destinations[from],
+            // Negative destinations can't be a machine, send them out of the cluster.
+            Self::Packets { .. } => usize::try_from(values.remove(0)).unwrap_or(usize::MAX),
+        };
+
+        Message { from, to, values }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+    pub from: Address,
+    pub to: Address,
+    pub values: Vec<i64>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Event<'a> {
+    /// A message was sent during the last round, and already delivered.
+    Message(&'a Message),
+    /// No machine received or sent anything during the last round.
+    Idle,
+}
+
+/// A set of Intcode machines, run in a round-robin fashion, whose outputs are sent to
+/// the input queues of each other.
+pub struct Cluster {
+    machines: Vec<IntcodeComputer>,
+    halted: Vec<bool>,
+    partial_outputs: Vec<Vec<i64>>,
+    routing: Routing,
+    idle_input: Option<i64>,
+}
+
+impl Cluster {
+    /// `len` copies of the same program, with empty input queues.
+    pub fn new(program: &[i64], len: usize, routing: Routing) -> Self {
+        Self {
+            machines: (0..len)
+                .map(|_| IntcodeComputer::from(program, vec![]))
+                .collect(),
+            halted: vec![false; len],
+            partial_outputs: vec![vec![]; len],
+            routing,
+            idle_input: None,
+        }
+    }
+
+    /// Value read by a machine that waits for input while its queue is empty, instead
+    /// of blocking until the next round (e.g. -1 for the network of 2019 day 23).
+    pub fn with_idle_input(mut self, value: i64) -> Self {
+        self.idle_input = Some(value);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.machines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.machines.is_empty()
+    }
+
+    pub fn machine(&self, address: Address) -> &IntcodeComputer {
+        &self.machines[address]
+    }
+
+    pub fn machine_mut(&mut self, address: Address) -> &mut IntcodeComputer {
+        &mut self.machines[address]
+    }
+
+    pub fn is_halted(&self, address: Address) -> bool {
+        self.halted[address]
+    }
+
+    pub fn all_halted(&self) -> bool {
+        self.halted.iter().all(|&h| h)
+    }
+
+    /// Appends values to the input queue of a machine.
+    pub fn send(&mut self, address: Address, values: &[i64]) {
+        self.machines[address].provide_input(values.to_vec());
+    }
+
+    fn deliver(&mut self, message: &Message) {
+        if message.to < self.machines.len() {
+            self.send(message.to, &message.values);
+        }
+    }
+
+    /// Runs every machine once, in order, until it halts or waits for an empty input
+    /// queue. Returns the messages sent during the round, and whether it was idle.
+    pub fn run_round(&mut self) -> (Vec<Message>, bool) {
+        let mut messages = vec![];
+        let mut idle = true;
+
+        for address in 0..self.machines.len() {
+            if self.halted[address] {
+                continue;
+            }
+
+            if !self.machines[address].input.is_empty() {
+                idle = false;
+            } else if let Some(value) = self.idle_input {
+                self.machines[address].provide_input(vec![value]);
+            }
+
+            loop {
+                match self.machines[address].process() {
+                    State::Runnable => {}
+                    State::WaitingForInput => break,
+                    State::Halted => {
+                        self.halted[address] = true;
+                        break;
+                    }
+                    State::GaveOutput(value) => {
+                        idle = false;
+
+                        let partial = &mut self.partial_outputs[address];
+                        partial.push(value);
+
+                        if partial.len() == self.routing.message_size() {
+                            let values = std::mem::take(partial);
+                            let message = self.routing.route(address, values);
+
+                            self.deliver(&message);
+                            messages.push(message);
+                        }
+                    }
+                }
+            }
+        }
+
+        (messages, idle)
+    }
+
+    /// Runs rounds, calling `hook` on every message and after every idle round, until
+    /// it returns something. The hook can send messages itself, e.g. to wake up the
+    /// network when it's idle. Returns `None` when every machine halted, or when the
+    /// cluster is idle without any pending input and no idle input to feed.
+    pub fn run_until<T, F>(&mut self, mut hook: F) -> Option<T>
+    where
+        F: FnMut(&mut Self, Event<'_>) -> Option<T>,
+    {
+        loop {
+            let (messages, idle) = self.run_round();
+
+            for message in &messages {
+                if let Some(result) = hook(self, Event::Message(message)) {
+                    return Some(result);
+                }
+            }
+
+            if idle {
+                if let Some(result) = hook(self, Event::Idle) {
+                    return Some(result);
+                }
+
+                let stuck =
+                    self.idle_input.is_none() && self.machines.iter().all(|m| m.input.is_empty());
+
+                if stuck {
+                    return None;
+                }
+            }
+
+            if self.all_halted() {
+                return None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2019::intcode_assembly::assemble;
+
+    /// Reads its address, then forwards every `(x, y)` packet it receives to the next
+    /// address as `(x, y + 1)`.
+    const FORWARDER: &str = "
+                IN @addr
+                ADD @addr, #1, @next
+        loop:   IN @x
+                EQ @x, #-1, @tmp
+                JT @tmp, #loop
+                IN @y
+                ADD @y, #1, @y
+                OUT @next
+                OUT @x
+                OUT @y
+                JT #1, #loop
+        addr:   DATA 0
+        next:   DATA 0
+        x:      DATA 0
+        y:      DATA 0
+        tmp:    DATA 0
+    ";
+
+    #[test]
+    fn test_packets() {
+        let program = assemble(FORWARDER).unwrap();
+        let mut cluster =
+            Cluster::new(&program, 3, Routing::Packets { size: 3 }).with_idle_input(-1);
+
+        for address in 0..3 {
+            cluster.send(address, &[address as i64]);
+        }
+        cluster.send(0, &[7, 0]);
+
+        let mut traffic = vec![];
+        let out = cluster.run_until(|_, event| match event {
+            Event::Message(m) => {
+                traffic.push((m.from, m.to));
+                (m.to == 3).then(|| m.values.clone())
+            }
+            Event::Idle => None,
+        });
+
+        assert_eq!(Some(vec![7, 3]), out);
+        assert_eq!(vec![(0, 1), (1, 2), (2, 3)], traffic);
+
+        // Wake up the network once it's idle, and stop the second time.
+        let mut woken = false;
+        let out = cluster.run_until(|c, event| match event {
+            Event::Message(m) if m.to == 3 => Some(m.values[1]),
+            Event::Idle if !woken => {
+                woken = true;
+                c.send(1, &[8, 10]);
+                None
+            }
+            _ => None,
+        });
+
+        assert!(woken);
+        assert_eq!(Some(12), out);
+    }
+
+    #[test]
+    fn test_ring() {
+        // Adds its first input to every other input, until it reads a 0.
+        let program = assemble(
+            "
+                IN @inc
+        loop:   IN @x
+                JF @x, #end
+                ADD @x, @inc, @x
+                OUT @x
+                JT #1, #loop
+        end:    HALT
+        inc:    DATA 0
+        x:      DATA 0
+            ",
+        )
+        .unwrap();
+        let mut cluster = Cluster::new(&program, 2, Routing::ring(2));
+        cluster.send(0, &[1, 5]);
+        cluster.send(1, &[-6]);
+
+        // 5 -> 6 -> 0, machine 0 stops, then machine 1 gets stuck.
+        let mut sent = vec![];
+        let out: Option<()> = cluster.run_until(|_, event| {
+            if let Event::Message(m) = event {
+                sent.push(m.values[0]);
+            }
+            None
+        });
+
+        assert_eq!(None, out);
+        assert_eq!(vec![6, 0], sent);
+        assert!(cluster.is_halted(0));
+        assert!(!cluster.is_halted(1));
+    }
+}
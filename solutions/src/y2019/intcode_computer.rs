@@ -1,16 +1,107 @@
 use log::debug;
 
-use crate::y2019::helpers::commons::get_nth_digit;
+use crate::y2019::{
+    helpers::commons::get_nth_digit,
+    intcode_memory::{Memory, MemoryError},
+};
 
-#[derive(Copy, Clone, Debug, PartialEq)]
-enum ParameterMode {
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ParameterMode {
     Position = 0,
     Immediate = 1,
     Relative = 2,
 }
 
+impl ParameterMode {
+    pub fn from_digit(digit: i64) -> Option<Self> {
+        match digit {
+            0 => Some(Self::Position),
+            1 => Some(Self::Immediate),
+            2 => Some(Self::Relative),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Opcode {
+    Add = 1,
+    Mul = 2,
+    Input = 3,
+    Output = 4,
+    JumpIfTrue = 5,
+    JumpIfFalse = 6,
+    LessThan = 7,
+    Equals = 8,
+    AdjustRelativeBase = 9,
+    Halt = 99,
+}
+
+impl Opcode {
+    pub const ALL: [Self; 10] = [
+        Self::Add,
+        Self::Mul,
+        Self::Input,
+        Self::Output,
+        Self::JumpIfTrue,
+        Self::JumpIfFalse,
+        Self::LessThan,
+        Self::Equals,
+        Self::AdjustRelativeBase,
+        Self::Halt,
+    ];
+
+    /// Opcode of an instruction, ignoring its parameter modes.
+    pub fn from_instruction(instruction: i64) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|&op| op as i64 == instruction % 100)
+    }
+
+    pub fn parameter_count(self) -> usize {
+        match self {
+            Self::Add | Self::Mul | Self::LessThan | Self::Equals => 3,
+            Self::JumpIfTrue | Self::JumpIfFalse => 2,
+            Self::Input | Self::Output | Self::AdjustRelativeBase => 1,
+            Self::Halt => 0,
+        }
+    }
+
+    /// Whether the parameter at `index` is an address that gets written to.
+    pub fn writes_parameter(self, index: usize) -> bool {
+        match self {
+            Self::Add | Self::Mul | Self::LessThan | Self::Equals => index == 2,
+            Self::Input => index == 0,
+            _ => false,
+        }
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Self::Add => "ADD",
+            Self::Mul => "MUL",
+            Self::Input => "IN",
+            Self::Output => "OUT",
+            Self::JumpIfTrue => "JT",
+            Self::JumpIfFalse => "JF",
+            Self::LessThan => "LT",
+            Self::Equals => "EQ",
+            Self::AdjustRelativeBase => "ARB",
+            Self::Halt => "HALT",
+        }
+    }
+
+    pub fn from_mnemonic(mnemonic: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|op| op.mnemonic().eq_ignore_ascii_case(mnemonic))
+    }
+}
+
 #[allow(unused)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum State {
     Runnable,
     Halted,
@@ -18,10 +109,19 @@ pub enum State {
     GaveOutput(i64),
 }
 
-pub const MEMORY_SIZE: usize = 65536;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum IntcodeError {
+    #[error(transparent)]
+    Memory(#[from] MemoryError),
+    #[error("unknown instruction {instruction} at {address}")]
+    UnknownInstruction { address: usize, instruction: i64 },
+    #[error("write in immediate mode at {0}")]
+    ImmediateWrite(usize),
+}
+
+#[derive(Clone)]
 pub struct IntcodeComputer {
-    initial_state: Vec<i64>,
-    memory: Vec<i64>,
+    memory: Memory,
     instruction_pointer: usize,
     pub input: Vec<i64>,
     relative_base: isize,
@@ -29,14 +129,8 @@ pub struct IntcodeComputer {
 
 impl IntcodeComputer {
     pub fn from(data: &[i64], input: Vec<i64>) -> Self {
-        // Extend the program's memory
-        let mut memory = data.to_vec();
-        memory.extend(vec![0; MEMORY_SIZE - data.len()]);
-        let initial_state = memory.clone();
-
         Self {
-            initial_state,
-            memory,
+            memory: Memory::new(data),
             instruction_pointer: 0,
             input,
             relative_base: 0,
@@ -49,16 +143,19 @@ impl IntcodeComputer {
         c.process()
     }
 
+    fn opcode(&self) -> Result<Opcode, IntcodeError> {
+        let instruction = self.memory.get(self.instruction_pointer);
+
+        Opcode::from_instruction(instruction).ok_or(IntcodeError::UnknownInstruction {
+            address: self.instruction_pointer,
+            instruction,
+        })
+    }
+
     fn get_instruction_length(&self) -> usize {
-        match self.memory[self.instruction_pointer] % 100 {
-            1 | 2 | 7 | 8 => 4,
-            3 | 4 | 9 => 2,
-            5 | 6 => 3,
-            99 => 1,
-            _ => panic!(
-                "Unknown instruction: {}",
-                self.memory[self.instruction_pointer] % 100
-            ),
+        match self.opcode() {
+            Ok(opcode) => opcode.parameter_count() + 1,
+            Err(e) => panic!("{}", e),
         }
     }
 
@@ -68,7 +165,11 @@ impl IntcodeComputer {
         let mut r = vec![ParameterMode::Position; len];
 
         (0..len).for_each(|i| {
-            r[i] = match get_nth_digit(self.memory[self.instruction_pointer] as u32, i + 2, false) {
+            r[i] = match get_nth_digit(
+                self.memory.get(self.instruction_pointer) as u32,
+                i + 2,
+                false,
+            ) {
                 Some(1) => ParameterMode::Immediate,
                 Some(2) => ParameterMode::Relative,
                 _ => ParameterMode::Position,
@@ -78,152 +179,170 @@ impl IntcodeComputer {
         r
     }
 
-    fn read(&self, param: i64, parameter_mode: ParameterMode) -> i64 {
-        match parameter_mode {
-            ParameterMode::Position => self.memory[param as usize],
+    /// Raw value of the n-th parameter of the current instruction.
+    fn param(&self, n: usize) -> i64 {
+        self.memory.get(self.instruction_pointer + n)
+    }
+
+    fn read(&self, param: i64, parameter_mode: ParameterMode) -> Result<i64, IntcodeError> {
+        Ok(match parameter_mode {
+            ParameterMode::Position => self.memory.read(param)?,
             ParameterMode::Immediate => param,
-            ParameterMode::Relative => self.memory[(param + self.relative_base as i64) as usize],
-        }
+            ParameterMode::Relative => self.memory.read(param + self.relative_base as i64)?,
+        })
     }
 
-    fn write(&mut self, param: i64, parameter_mode: ParameterMode, value: i64) {
+    fn write(
+        &mut self,
+        param: i64,
+        parameter_mode: ParameterMode,
+        value: i64,
+    ) -> Result<(), IntcodeError> {
         let addr = match parameter_mode {
-            ParameterMode::Position => param as usize,
-            ParameterMode::Relative => (param + self.relative_base as i64) as usize,
-            ParameterMode::Immediate => panic!("Unsupported parameter mode: {:?}", parameter_mode),
+            ParameterMode::Position => param,
+            ParameterMode::Relative => param + self.relative_base as i64,
+            ParameterMode::Immediate => {
+                return Err(IntcodeError::ImmediateWrite(self.instruction_pointer))
+            }
         };
 
-        // TODO: Handle address too big
+        Ok(self.memory.write(addr, value)?)
+    }
+
+    fn jump(&mut self, target: i64) -> Result<(), IntcodeError> {
+        if target < 0 {
+            return Err(MemoryError::NegativeAddress(target).into());
+        }
+
+        self.instruction_pointer = target as usize;
 
-        self.memory[addr] = value;
+        Ok(())
     }
 
     pub fn process(&mut self) -> State {
-        use State::{GaveOutput, Halted, WaitingForInput};
+        match self.try_process() {
+            Ok(state) => state,
+            Err(e) => panic!("{}", e),
+        }
+    }
 
+    pub fn try_process(&mut self) -> Result<State, IntcodeError> {
         loop {
-            let p = self.get_parameter_modes();
+            match self.try_step()? {
+                State::Runnable => {}
+                state => return Ok(state),
+            }
+        }
+    }
 
-            match self.memory[self.instruction_pointer] % 100 {
-                1 => {
-                    // Add
-                    debug!("[{0:4}] ADD", self.instruction_pointer);
+    #[allow(unused)]
+    pub fn step(&mut self) -> State {
+        match self.try_step() {
+            Ok(state) => state,
+            Err(e) => panic!("{}", e),
+        }
+    }
 
-                    let op1 = self.read(self.memory[self.instruction_pointer + 1], p[0]);
-                    let op2 = self.read(self.memory[self.instruction_pointer + 2], p[1]);
-                    self.write(self.memory[self.instruction_pointer + 3], p[2], op1 + op2);
+    /// Executes a single instruction. Returns `Runnable` when it neither needs input,
+    /// gave an output, nor halted.
+    pub fn try_step(&mut self) -> Result<State, IntcodeError> {
+        use State::{GaveOutput, Halted, Runnable, WaitingForInput};
 
-                    self.instruction_pointer += 4;
-                }
-                2 => {
-                    // Mul
-                    debug!("[{0:4}] MUL", self.instruction_pointer);
+        let opcode = self.opcode()?;
+        let p = self.get_parameter_modes();
 
-                    let op1 = self.read(self.memory[self.instruction_pointer + 1], p[0]);
-                    let op2 = self.read(self.memory[self.instruction_pointer + 2], p[1]);
-                    self.write(self.memory[self.instruction_pointer + 3], p[2], op1 * op2);
+        match opcode {
+            Opcode::Add => {
+                debug!("[{0:4}] ADD", self.instruction_pointer);
 
-                    self.instruction_pointer += 4;
-                }
-                3 => {
-                    // Input
-                    debug!("[{0:4}] INPUT", self.instruction_pointer);
-
-                    if self.input.is_empty() {
-                        return WaitingForInput;
-                    }
-
-                    self.write(
-                        self.memory[self.instruction_pointer + 1],
-                        p[0],
-                        self.input[0],
-                    );
-                    self.input = self.input.drain(1..).collect();
-
-                    self.instruction_pointer += 2;
-                }
-                4 => {
-                    // Output
+                let op1 = self.read(self.param(1), p[0])?;
+                let op2 = self.read(self.param(2), p[1])?;
+                self.write(self.param(3), p[2], op1 + op2)?;
 
-                    let output = self.read(self.memory[self.instruction_pointer + 1], p[0]);
-                    debug!("[{0:4}] OUTPUT {1}", self.instruction_pointer, output);
+                self.instruction_pointer += 4;
+            }
+            Opcode::Mul => {
+                debug!("[{0:4}] MUL", self.instruction_pointer);
 
-                    self.instruction_pointer += 2;
-                    return GaveOutput(output);
-                }
-                5 => {
-                    // Jump if true
-                    debug!("[{0:4}] JUMP IF TRUE", self.instruction_pointer);
-
-                    if self.read(self.memory[self.instruction_pointer + 1], p[0]) == 0 {
-                        self.instruction_pointer += 3;
-                    } else {
-                        self.instruction_pointer =
-                            self.read(self.memory[self.instruction_pointer + 2], p[1]) as usize;
-                    }
-                }
-                6 => {
-                    // Jump if false
-                    debug!("[{0:4}] JUMP IF FALSE", self.instruction_pointer);
-
-                    if self.read(self.memory[self.instruction_pointer + 1], p[0]) == 0 {
-                        self.instruction_pointer =
-                            self.read(self.memory[self.instruction_pointer + 2], p[1]) as usize;
-                    } else {
-                        self.instruction_pointer += 3;
-                    }
-                }
-                7 => {
-                    // Less than
-                    debug!("[{0:4}] LESS THAN", self.instruction_pointer);
-
-                    if self.read(self.memory[self.instruction_pointer + 1], p[0])
-                        < self.read(self.memory[self.instruction_pointer + 2], p[1])
-                    {
-                        self.write(self.memory[self.instruction_pointer + 3], p[2], 1);
-                    } else {
-                        self.write(self.memory[self.instruction_pointer + 3], p[2], 0);
-                    }
-
-                    self.instruction_pointer += 4;
-                }
-                8 => {
-                    // Equals
-                    debug!("[{0:4}] EQUALS", self.instruction_pointer);
-
-                    if self.read(self.memory[self.instruction_pointer + 1], p[0])
-                        == self.read(self.memory[self.instruction_pointer + 2], p[1])
-                    {
-                        self.write(self.memory[self.instruction_pointer + 3], p[2], 1);
-                    } else {
-                        self.write(self.memory[self.instruction_pointer + 3], p[2], 0);
-                    }
-
-                    self.instruction_pointer += 4;
+                let op1 = self.read(self.param(1), p[0])?;
+                let op2 = self.read(self.param(2), p[1])?;
+                self.write(self.param(3), p[2], op1 * op2)?;
+
+                self.instruction_pointer += 4;
+            }
+            Opcode::Input => {
+                debug!("[{0:4}] INPUT", self.instruction_pointer);
+
+                if self.input.is_empty() {
+                    return Ok(WaitingForInput);
                 }
-                9 => {
-                    // Relative base adjust
-                    let rba = self.read(self.memory[self.instruction_pointer + 1], p[0]);
-
-                    debug!(
-                        "[{0:4}] RELATIVE BASE ADJUST: {1} = {2}",
-                        self.instruction_pointer,
-                        rba,
-                        self.relative_base + rba as isize
-                    );
-
-                    self.relative_base += rba as isize;
-                    self.instruction_pointer += 2;
+
+                self.write(self.param(1), p[0], self.input[0])?;
+                self.input = self.input.drain(1..).collect();
+
+                self.instruction_pointer += 2;
+            }
+            Opcode::Output => {
+                let output = self.read(self.param(1), p[0])?;
+                debug!("[{0:4}] OUTPUT {1}", self.instruction_pointer, output);
+
+                self.instruction_pointer += 2;
+                return Ok(GaveOutput(output));
+            }
+            Opcode::JumpIfTrue => {
+                debug!("[{0:4}] JUMP IF TRUE", self.instruction_pointer);
+
+                if self.read(self.param(1), p[0])? == 0 {
+                    self.instruction_pointer += 3;
+                } else {
+                    self.jump(self.read(self.param(2), p[1])?)?;
                 }
-                99 => {
-                    debug!("[{0:4}] HALT", self.instruction_pointer);
-                    break;
+            }
+            Opcode::JumpIfFalse => {
+                debug!("[{0:4}] JUMP IF FALSE", self.instruction_pointer);
+
+                if self.read(self.param(1), p[0])? == 0 {
+                    self.jump(self.read(self.param(2), p[1])?)?;
+                } else {
+                    self.instruction_pointer += 3;
                 }
-                _ => panic!("Unknown intruction"),
+            }
+            Opcode::LessThan => {
+                debug!("[{0:4}] LESS THAN", self.instruction_pointer);
+
+                let less = self.read(self.param(1), p[0])? < self.read(self.param(2), p[1])?;
+                self.write(self.param(3), p[2], less as i64)?;
+
+                self.instruction_pointer += 4;
+            }
+            Opcode::Equals => {
+                debug!("[{0:4}] EQUALS", self.instruction_pointer);
+
+                let equal = self.read(self.param(1), p[0])? == self.read(self.param(2), p[1])?;
+                self.write(self.param(3), p[2], equal as i64)?;
+
+                self.instruction_pointer += 4;
+            }
+            Opcode::AdjustRelativeBase => {
+                let rba = self.read(self.param(1), p[0])?;
+
+                debug!(
+                    "[{0:4}] RELATIVE BASE ADJUST: {1} = {2}",
+                    self.instruction_pointer,
+                    rba,
+                    self.relative_base + rba as isize
+                );
+
+                self.relative_base += rba as isize;
+                self.instruction_pointer += 2;
+            }
+            Opcode::Halt => {
+                debug!("[{0:4}] HALT", self.instruction_pointer);
+                return Ok(Halted);
             }
         }
 
-        Halted
+        Ok(Runnable)
     }
 
     #[allow(unused)]
@@ -232,26 +351,51 @@ impl IntcodeComputer {
     }
 
     pub fn patch_memory(&mut self, index: usize, value: i64) {
-        self.memory[index] = value;
+        self.memory.set(index, value);
     }
 
     pub fn index(&mut self, index: usize) -> i64 {
-        self.memory[index]
+        self.memory.get(index)
     }
 
     pub fn reinitialize_memory(&mut self) {
-        self.memory = self.initial_state.clone();
+        self.memory.reset();
         self.instruction_pointer = 0;
         self.input = vec![];
         self.relative_base = 0;
     }
 
     #[allow(unused)]
-    pub fn get_memory(&self) -> &Vec<i64> {
+    pub fn get_memory(&self) -> &Memory {
         &self.memory
     }
+
+    /// Saves the whole state of the machine. Memory is only copied when either
+    /// side writes to it.
+    #[allow(unused)]
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot(self.clone())
+    }
+
+    #[allow(unused)]
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        *self = snapshot.0.clone();
+    }
+
+    #[allow(unused)]
+    pub fn instruction_pointer(&self) -> usize {
+        self.instruction_pointer
+    }
+
+    #[allow(unused)]
+    pub fn relative_base(&self) -> isize {
+        self.relative_base
+    }
 }
 
+#[derive(Clone)]
+pub struct Snapshot(IntcodeComputer);
+
 impl Iterator for IntcodeComputer {
     type Item = i64;
     fn next(&mut self) -> Option<i64> {
@@ -265,17 +409,29 @@ impl Iterator for IntcodeComputer {
 #[cfg(test)]
 mod test_intcode_computer {
     use super::*;
+    use crate::y2019::intcode_assembly::assemble;
 
-    // Tests have been disabled because the "if target == current then do not increment
-    // intruction pointer" modification seems to have broken these basic tests.
-    //#[test]
-    //fn test_basics() {
-    //    // These test the memory
-    //    assert_eq!(process_from(vec![1,0,0,0,99], 0).1, vec![2,0,0,0,99]);
-    //    assert_eq!(process_from(vec![2,3,0,3,99], 0).1, vec![2,3,0,6,99]);
-    //    assert_eq!(process_from(vec![2,4,4,5,99,0], 0).1, vec![2,4,4,5,99,9801]);
-    //    assert_eq!(process_from(vec![1,1,1,4,99,5,6,0,99], 0).1, vec![30,1,1,4,2,5,6,0,99]);
-    //}
+    fn run_to_memory(program: &[i64]) -> Vec<i64> {
+        let mut c = IntcodeComputer::from(program, vec![]);
+        assert_eq!(State::Halted, c.process());
+
+        c.get_memory().program().to_vec()
+    }
+
+    #[test]
+    fn test_basics() {
+        // These test the memory
+        assert_eq!(run_to_memory(&[1, 0, 0, 0, 99]), vec![2, 0, 0, 0, 99]);
+        assert_eq!(run_to_memory(&[2, 3, 0, 3, 99]), vec![2, 3, 0, 6, 99]);
+        assert_eq!(
+            run_to_memory(&[2, 4, 4, 5, 99, 0]),
+            vec![2, 4, 4, 5, 99, 9801]
+        );
+        assert_eq!(
+            run_to_memory(&[1, 1, 1, 4, 99, 5, 6, 0, 99]),
+            vec![30, 1, 1, 4, 2, 5, 6, 0, 99]
+        );
+    }
 
     #[test]
     fn test_get_parameter_mode() {
@@ -290,130 +446,98 @@ mod test_intcode_computer {
         );
     }
 
+    fn run_assembly(source: &str, input: i64) -> State {
+        IntcodeComputer::process_from(&assemble(source).unwrap(), vec![input])
+    }
+
     #[test]
     fn test_comparisons() {
+        const EQUALS: &str = "
+                IN @input
+                EQ @input, @eight, @input
+                OUT @input
+                HALT
+        input:  DATA -1
+        eight:  DATA 8";
+
+        const LESS_THAN: &str = "
+                IN @input
+                LT @input, @eight, @input
+                OUT @input
+                HALT
+        input:  DATA -1
+        eight:  DATA 8";
+
+        const EQUALS_IMMEDIATE: &str = "
+                IN @input
+                EQ @input, #8, @input
+                OUT @input
+                HALT
+        input:  DATA -1";
+
+        const LESS_THAN_IMMEDIATE: &str = "
+                IN @input
+                LT @input, #8, @input
+                OUT @input
+                HALT
+        input:  DATA -1";
+
         // Test input == 8
-        assert_eq!(
-            State::GaveOutput(1),
-            IntcodeComputer::process_from(&[3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8], vec![8])
-        );
-        assert_eq!(
-            State::GaveOutput(0),
-            IntcodeComputer::process_from(&[3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8], vec![0])
-        );
+        assert_eq!(State::GaveOutput(1), run_assembly(EQUALS, 8));
+        assert_eq!(State::GaveOutput(0), run_assembly(EQUALS, 0));
 
         // Test input < 8
-        assert_eq!(
-            State::GaveOutput(1),
-            IntcodeComputer::process_from(&[3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8], vec![3])
-        );
-        assert_eq!(
-            State::GaveOutput(1),
-            IntcodeComputer::process_from(&[3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8], vec![5])
-        );
-        assert_eq!(
-            State::GaveOutput(0),
-            IntcodeComputer::process_from(&[3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8], vec![8])
-        );
-        assert_eq!(
-            State::GaveOutput(0),
-            IntcodeComputer::process_from(&[3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8], vec![9])
-        );
+        assert_eq!(State::GaveOutput(1), run_assembly(LESS_THAN, 3));
+        assert_eq!(State::GaveOutput(1), run_assembly(LESS_THAN, 5));
+        assert_eq!(State::GaveOutput(0), run_assembly(LESS_THAN, 8));
+        assert_eq!(State::GaveOutput(0), run_assembly(LESS_THAN, 9));
 
         // Test input == 8 (immediate)
-        assert_eq!(
-            State::GaveOutput(0),
-            IntcodeComputer::process_from(&[3, 3, 1108, -1, 8, 3, 4, 3, 99], vec![5])
-        );
-        assert_eq!(
-            State::GaveOutput(0),
-            IntcodeComputer::process_from(&[3, 3, 1108, -1, 8, 3, 4, 3, 99], vec![9])
-        );
-        assert_eq!(
-            State::GaveOutput(1),
-            IntcodeComputer::process_from(&[3, 3, 1108, -1, 8, 3, 4, 3, 99], vec![8])
-        );
+        assert_eq!(State::GaveOutput(0), run_assembly(EQUALS_IMMEDIATE, 5));
+        assert_eq!(State::GaveOutput(0), run_assembly(EQUALS_IMMEDIATE, 9));
+        assert_eq!(State::GaveOutput(1), run_assembly(EQUALS_IMMEDIATE, 8));
 
         // Test input < 8 (immediate)
-        assert_eq!(
-            State::GaveOutput(0),
-            IntcodeComputer::process_from(&[3, 3, 1107, -1, 8, 3, 4, 3, 99], vec![12])
-        );
-        assert_eq!(
-            State::GaveOutput(0),
-            IntcodeComputer::process_from(&[3, 3, 1107, -1, 8, 3, 4, 3, 99], vec![8])
-        );
-        assert_eq!(
-            State::GaveOutput(1),
-            IntcodeComputer::process_from(&[3, 3, 1107, -1, 8, 3, 4, 3, 99], vec![3])
-        );
-        assert_eq!(
-            State::GaveOutput(1),
-            IntcodeComputer::process_from(&[3, 3, 1107, -1, 8, 3, 4, 3, 99], vec![5])
-        );
+        assert_eq!(State::GaveOutput(0), run_assembly(LESS_THAN_IMMEDIATE, 12));
+        assert_eq!(State::GaveOutput(0), run_assembly(LESS_THAN_IMMEDIATE, 8));
+        assert_eq!(State::GaveOutput(1), run_assembly(LESS_THAN_IMMEDIATE, 3));
+        assert_eq!(State::GaveOutput(1), run_assembly(LESS_THAN_IMMEDIATE, 5));
     }
 
     #[test]
     fn test_jumps() {
+        // Outputs 0 if the input is 0, 1 otherwise.
+        const JUMP: &str = "
+                IN @input
+                JF @input, @target
+                ADD @output, @one, @output
+        out:    OUT @output
+                HALT
+        input:  DATA -1
+        output: DATA 0
+        one:    DATA 1
+        target: DATA out";
+
+        const JUMP_IMMEDIATE: &str = "
+                IN @input
+                JT @input, #out
+                ADD #0, #0, @output
+        out:    OUT @output
+                HALT
+        input:  DATA -1
+        output: DATA 1";
+
         // Test jump
-        assert_eq!(
-            State::GaveOutput(0),
-            IntcodeComputer::process_from(
-                &[3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9],
-                vec![0]
-            )
-        );
-        assert_eq!(
-            State::GaveOutput(1),
-            IntcodeComputer::process_from(
-                &[3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9],
-                vec![1]
-            )
-        );
-        assert_eq!(
-            State::GaveOutput(1),
-            IntcodeComputer::process_from(
-                &[3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9],
-                vec![87]
-            )
-        );
-        assert_eq!(
-            State::GaveOutput(1),
-            IntcodeComputer::process_from(
-                &[3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9],
-                vec![99]
-            )
-        );
+        assert_eq!(State::GaveOutput(0), run_assembly(JUMP, 0));
+        assert_eq!(State::GaveOutput(1), run_assembly(JUMP, 1));
+        assert_eq!(State::GaveOutput(1), run_assembly(JUMP, 87));
+        assert_eq!(State::GaveOutput(1), run_assembly(JUMP, 99));
 
         // Test jump (immediate)
-        assert_eq!(
-            State::GaveOutput(0),
-            IntcodeComputer::process_from(
-                &[3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1],
-                vec![0]
-            )
-        );
-        assert_eq!(
-            State::GaveOutput(1),
-            IntcodeComputer::process_from(
-                &[3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1],
-                vec![1]
-            )
-        );
-        assert_eq!(
-            State::GaveOutput(1),
-            IntcodeComputer::process_from(
-                &[3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1],
-                vec![87]
-            )
-        );
-        assert_eq!(
-            State::GaveOutput(1),
-            IntcodeComputer::process_from(
-                &[3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1],
-                vec![99]
-            )
-        );
+        assert_eq!(State::GaveOutput(0), run_assembly(JUMP_IMMEDIATE, 0));
+        assert_eq!(State::GaveOutput(1), run_assembly(JUMP_IMMEDIATE, 1));
+        assert_eq!(State::GaveOutput(1), run_assembly(JUMP_IMMEDIATE, 87));
+        assert_eq!(State::GaveOutput(1), run_assembly(JUMP_IMMEDIATE, 99));
     }
 
     #[test]
@@ -452,4 +576,51 @@ mod test_intcode_computer {
             IntcodeComputer::process_from(TEST_VEC_1, vec![9900])
         );
     }
+
+    #[test]
+    fn test_snapshot() {
+        // Outputs the sum of every input so far.
+        let program = &[3, 11, 1, 11, 12, 12, 4, 12, 1105, 1, 0, 0, 0];
+        let mut c = IntcodeComputer::from(program, vec![1, 2]);
+
+        assert_eq!(State::GaveOutput(1), c.process());
+        let snapshot = c.snapshot();
+
+        assert_eq!(State::GaveOutput(3), c.process());
+        c.provide_input(vec![10]);
+        assert_eq!(State::GaveOutput(13), c.process());
+
+        c.restore(&snapshot);
+        assert_eq!(State::GaveOutput(3), c.process());
+        assert_eq!(State::WaitingForInput, c.process());
+    }
+
+    #[test]
+    fn test_memory_errors() {
+        // Write past the old 64K limit, then read it back.
+        let mut c = IntcodeComputer::from(&[1101, 20, 22, 100_000, 4, 100_000, 99], vec![]);
+        assert_eq!(State::GaveOutput(42), c.process());
+        assert_eq!(42, c.index(100_000));
+
+        let mut c = IntcodeComputer::from(&[4, -3, 99], vec![]);
+        assert_eq!(
+            Err(IntcodeError::Memory(MemoryError::NegativeAddress(-3))),
+            c.try_process()
+        );
+
+        let mut c = IntcodeComputer::from(&[1105, 1, -1], vec![]);
+        assert_eq!(
+            Err(IntcodeError::Memory(MemoryError::NegativeAddress(-1))),
+            c.try_process()
+        );
+
+        let mut c = IntcodeComputer::from(&[42], vec![]);
+        assert_eq!(
+            Err(IntcodeError::UnknownInstruction {
+                address: 0,
+                instruction: 42
+            }),
+            c.try_process()
+        );
+    }
 }
@@ -0,0 +1,374 @@
+#![allow(unused)]
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Display,
+};
+
+use crate::y2019::{
+    intcode_assembly::Instruction,
+    intcode_computer::{IntcodeComputer, IntcodeError, Opcode, ParameterMode, State},
+};
+
+/// Why the debugger gave control back.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Stop {
+    /// What the machine itself reports, `Runnable` after a step that's not special.
+    State(State),
+    Breakpoint(usize),
+    OpcodeBreakpoint {
+        address: usize,
+        opcode: Opcode,
+    },
+    /// Stops right after the write.
+    Watchpoint {
+        address: usize,
+        old: i64,
+        new: i64,
+    },
+    /// In strict mode, the instruction at `address` is about to overwrite its own
+    /// encoding, so the result depends on how the instruction pointer is moved.
+    SelfModifying {
+        address: usize,
+        target: usize,
+    },
+    Error(IntcodeError),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub instruction: Instruction,
+    pub relative_base: isize,
+}
+
+impl Display for TraceEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{:4}] {:<32} rb={}",
+            self.instruction.address,
+            self.instruction.to_string(),
+            self.relative_base
+        )
+    }
+}
+
+/// Runs an `IntcodeComputer` one instruction at a time, with breakpoints,
+/// watchpoints, an execution trace and instruction counts.
+pub struct Debugger {
+    pub computer: IntcodeComputer,
+    breakpoints: HashSet<usize>,
+    opcode_breakpoints: HashSet<Opcode>,
+    watchpoints: HashSet<usize>,
+    strict: bool,
+    trace: VecDeque<TraceEntry>,
+    trace_limit: usize,
+    hits: HashMap<usize, usize>,
+    opcode_counts: HashMap<Opcode, usize>,
+    executed: usize,
+    stopped_at: Option<usize>,
+    halted_at: Option<usize>,
+}
+
+impl Debugger {
+    pub fn new(computer: IntcodeComputer) -> Self {
+        Self {
+            computer,
+            breakpoints: HashSet::new(),
+            opcode_breakpoints: HashSet::new(),
+            watchpoints: HashSet::new(),
+            strict: false,
+            trace: VecDeque::new(),
+            trace_limit: 0,
+            hits: HashMap::new(),
+            opcode_counts: HashMap::new(),
+            executed: 0,
+            stopped_at: None,
+            halted_at: None,
+        }
+    }
+
+    pub fn break_at(&mut self, address: usize) {
+        self.breakpoints.insert(address);
+    }
+
+    pub fn break_on(&mut self, opcode: Opcode) {
+        self.opcode_breakpoints.insert(opcode);
+    }
+
+    pub fn watch(&mut self, address: usize) {
+        self.watchpoints.insert(address);
+    }
+
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+        self.opcode_breakpoints.clear();
+        self.watchpoints.clear();
+    }
+
+    /// Stops before any instruction that writes into itself.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Keeps the last `limit` executed instructions.
+    pub fn enable_trace(&mut self, limit: usize) {
+        self.trace_limit = limit;
+        while self.trace.len() > limit {
+            self.trace.pop_front();
+        }
+    }
+
+    pub fn trace(&self) -> impl Iterator<Item = &TraceEntry> {
+        self.trace.iter()
+    }
+
+    /// Number of instructions executed so far.
+    pub fn executed(&self) -> usize {
+        self.executed
+    }
+
+    /// Number of times the instruction at `address` was executed.
+    pub fn hits(&self, address: usize) -> usize {
+        self.hits.get(&address).copied().unwrap_or(0)
+    }
+
+    pub fn opcode_counts(&self) -> &HashMap<Opcode, usize> {
+        &self.opcode_counts
+    }
+
+    /// Most executed addresses first.
+    pub fn hot_spots(&self) -> Vec<(usize, usize)> {
+        let mut hits = self.hits.iter().map(|(&a, &n)| (a, n)).collect::<Vec<_>>();
+        hits.sort_unstable_by_key(|&(address, count)| (std::cmp::Reverse(count), address));
+
+        hits
+    }
+
+    pub fn current_instruction(&self) -> Option<Instruction> {
+        let ip = self.computer.instruction_pointer();
+        let memory = self.computer.get_memory();
+        let window = (0..4).map(|i| memory.get(ip + i)).collect::<Vec<_>>();
+
+        Instruction::decode(&window, 0).map(|instruction| Instruction {
+            address: ip,
+            ..instruction
+        })
+    }
+
+    /// Address written by the current instruction, if any.
+    fn write_target(&self, instruction: &Instruction) -> Option<usize> {
+        let (i, &(mode, value)) = instruction
+            .parameters
+            .iter()
+            .enumerate()
+            .find(|(i, _)| instruction.opcode.writes_parameter(*i))?;
+
+        let address = match mode {
+            ParameterMode::Position => value,
+            ParameterMode::Relative => value + self.computer.relative_base() as i64,
+            ParameterMode::Immediate => return None,
+        };
+
+        Some(address).filter(|&a| a >= 0).map(|a| a as usize)
+    }
+
+    /// Executes a single instruction, ignoring breakpoints.
+    pub fn step(&mut self) -> Stop {
+        let instruction = self.current_instruction();
+
+        // Halting doesn't move the machine, there's nothing more to execute or count.
+        if let Some(i) = &instruction {
+            if i.opcode == Opcode::Halt && self.halted_at == Some(i.address) {
+                return Stop::State(State::Halted);
+            }
+        }
+
+        let target = instruction.as_ref().and_then(|i| self.write_target(i));
+        let old = target.map(|t| self.computer.get_memory().get(t));
+
+        let state = match self.computer.try_step() {
+            Ok(state) => state,
+            Err(e) => return Stop::Error(e),
+        };
+
+        if state == State::WaitingForInput {
+            return Stop::State(state);
+        }
+
+        if state == State::Halted {
+            self.halted_at = instruction.as_ref().map(|i| i.address);
+        }
+
+        if let Some(instruction) = instruction {
+            self.executed += 1;
+            *self.hits.entry(instruction.address).or_insert(0) += 1;
+            *self.opcode_counts.entry(instruction.opcode).or_insert(0) += 1;
+
+            if self.trace_limit > 0 {
+                if self.trace.len() == self.trace_limit {
+                    self.trace.pop_front();
+                }
+
+                self.trace.push_back(TraceEntry {
+                    instruction,
+                    relative_base: self.computer.relative_base(),
+                });
+            }
+        }
+
+        if let (Some(address), Some(old)) = (target, old) {
+            if self.watchpoints.contains(&address) {
+                let new = self.computer.get_memory().get(address);
+                return Stop::Watchpoint { address, old, new };
+            }
+        }
+
+        Stop::State(state)
+    }
+
+    fn check_before(&self) -> Option<Stop> {
+        let address = self.computer.instruction_pointer();
+
+        if self.breakpoints.contains(&address) {
+            return Some(Stop::Breakpoint(address));
+        }
+
+        let instruction = self.current_instruction()?;
+
+        if self.opcode_breakpoints.contains(&instruction.opcode) {
+            return Some(Stop::OpcodeBreakpoint {
+                address,
+                opcode: instruction.opcode,
+            });
+        }
+
+        match self.write_target(&instruction) {
+            Some(target)
+                if self.strict && (address..address + instruction.size()).contains(&target) =>
+            {
+                Some(Stop::SelfModifying { address, target })
+            }
+            _ => None,
+        }
+    }
+
+    /// Runs until something stops the machine. Calling it again after a breakpoint
+    /// resumes from there.
+    pub fn run(&mut self) -> Stop {
+        let resuming = self.stopped_at.take() == Some(self.computer.instruction_pointer());
+
+        if !resuming {
+            if let Some(stop) = self.check_before() {
+                self.stopped_at = Some(self.computer.instruction_pointer());
+                return stop;
+            }
+        }
+
+        loop {
+            match self.step() {
+                Stop::State(State::Runnable) => {}
+                stop => return stop,
+            }
+
+            if let Some(stop) = self.check_before() {
+                self.stopped_at = Some(self.computer.instruction_pointer());
+                return stop;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EQUALS_8: &[i64] = &[3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+
+    #[test]
+    fn test_breakpoints() {
+        let mut d = Debugger::new(IntcodeComputer::from(EQUALS_8, vec![8]));
+        d.break_at(2);
+        d.watch(9);
+        d.break_on(Opcode::Halt);
+
+        assert_eq!(
+            Stop::Watchpoint {
+                address: 9,
+                old: -1,
+                new: 8
+            },
+            d.run()
+        );
+        assert_eq!(Stop::Breakpoint(2), d.run());
+        assert_eq!(
+            Stop::Watchpoint {
+                address: 9,
+                old: 8,
+                new: 1
+            },
+            d.run()
+        );
+        assert_eq!(Stop::State(State::GaveOutput(1)), d.run());
+        assert_eq!(
+            Stop::OpcodeBreakpoint {
+                address: 8,
+                opcode: Opcode::Halt
+            },
+            d.run()
+        );
+        assert_eq!(3, d.executed());
+        assert_eq!(Stop::State(State::Halted), d.step());
+        assert_eq!(1, d.hits(2));
+    }
+
+    #[test]
+    fn test_step_after_halt() {
+        let mut d = Debugger::new(IntcodeComputer::from(EQUALS_8, vec![8]));
+
+        assert_eq!(Stop::State(State::GaveOutput(1)), d.run());
+        assert_eq!(Stop::State(State::Halted), d.run());
+        assert_eq!(4, d.executed());
+
+        assert_eq!(Stop::State(State::Halted), d.step());
+        assert_eq!(Stop::State(State::Halted), d.step());
+        assert_eq!(4, d.executed());
+        assert_eq!(1, d.hits(8));
+        assert_eq!(Some(&1), d.opcode_counts().get(&Opcode::Halt));
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut d = Debugger::new(IntcodeComputer::from(EQUALS_8, vec![3]));
+        d.enable_trace(2);
+
+        assert_eq!(Stop::State(State::GaveOutput(0)), d.run());
+        assert_eq!(
+            vec![
+                "[   2] EQ @9, @10, @9                   rb=0",
+                "[   6] OUT @9                           rb=0"
+            ],
+            d.trace().map(ToString::to_string).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(&1), d.opcode_counts().get(&Opcode::Input));
+    }
+
+    #[test]
+    fn test_strict() {
+        // The second instruction overwrites itself.
+        let program = &[1101, 0, 0, 9, 1, 4, 4, 4, 99, 0];
+
+        let mut d = Debugger::new(IntcodeComputer::from(program, vec![]));
+        assert_eq!(Stop::State(State::Halted), d.run());
+
+        let mut d = Debugger::new(IntcodeComputer::from(program, vec![]));
+        d.set_strict(true);
+        assert_eq!(
+            Stop::SelfModifying {
+                address: 4,
+                target: 4
+            },
+            d.run()
+        );
+    }
+}
@@ -0,0 +1,140 @@
+#![allow(unused)]
+
+use std::{collections::HashMap, sync::Arc};
+
+const PAGE_SIZE: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum MemoryError {
+    #[error("negative address: {0}")]
+    NegativeAddress(i64),
+}
+
+/// Memory of an Intcode computer. The cells of the program are dense and shared with
+/// the initial image until the first write, so cloning and resetting are cheap. Cells
+/// past the end of the program are allocated by pages, on the first write, and pages
+/// are shared between clones the same way.
+#[derive(Clone, Debug)]
+pub struct Memory {
+    image: Arc<Vec<i64>>,
+    dense: Arc<Vec<i64>>,
+    pages: HashMap<usize, Arc<[i64; PAGE_SIZE]>>,
+}
+
+impl Memory {
+    pub fn new(program: &[i64]) -> Self {
+        let image = Arc::new(program.to_vec());
+
+        Self {
+            dense: Arc::clone(&image),
+            image,
+            pages: HashMap::new(),
+        }
+    }
+
+    fn to_address(address: i64) -> Result<usize, MemoryError> {
+        if address < 0 {
+            Err(MemoryError::NegativeAddress(address))
+        } else {
+            Ok(address as usize)
+        }
+    }
+
+    pub fn read(&self, address: i64) -> Result<i64, MemoryError> {
+        Ok(self.get(Self::to_address(address)?))
+    }
+
+    pub fn write(&mut self, address: i64, value: i64) -> Result<(), MemoryError> {
+        self.set(Self::to_address(address)?, value);
+
+        Ok(())
+    }
+
+    /// Value at `address`; cells that were never written to are 0.
+    pub fn get(&self, address: usize) -> i64 {
+        if address < self.dense.len() {
+            return self.dense[address];
+        }
+
+        self.pages
+            .get(&(address / PAGE_SIZE))
+            .map_or(0, |page| page[address % PAGE_SIZE])
+    }
+
+    pub fn set(&mut self, address: usize, value: i64) {
+        if address < self.dense.len() {
+            Arc::make_mut(&mut self.dense)[address] = value;
+        } else {
+            let page = self
+                .pages
+                .entry(address / PAGE_SIZE)
+                .or_insert_with(|| Arc::new([0; PAGE_SIZE]));
+
+            Arc::make_mut(page)[address % PAGE_SIZE] = value;
+        }
+    }
+
+    /// Goes back to the initial image without copying it.
+    pub fn reset(&mut self) {
+        self.dense = Arc::clone(&self.image);
+        self.pages.clear();
+    }
+
+    /// Cells of the program, as currently modified.
+    pub fn program(&self) -> &[i64] {
+        &self.dense
+    }
+
+    pub fn allocated_pages(&self) -> usize {
+        self.pages.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_write() {
+        let mut m = Memory::new(&[1, 2, 3]);
+
+        assert_eq!(Ok(2), m.read(1));
+        assert_eq!(Ok(0), m.read(1 << 40));
+        assert_eq!(Err(MemoryError::NegativeAddress(-1)), m.read(-1));
+        assert_eq!(Err(MemoryError::NegativeAddress(-5)), m.write(-5, 0));
+        assert_eq!(0, m.allocated_pages());
+
+        m.write(1, 20).unwrap();
+        m.write(100_000, 7).unwrap();
+        m.write(100_001, 8).unwrap();
+
+        assert_eq!(&[1, 20, 3], m.program());
+        assert_eq!(Ok(7), m.read(100_000));
+        assert_eq!(Ok(8), m.read(100_001));
+        assert_eq!(1, m.allocated_pages());
+    }
+
+    #[test]
+    fn test_copy_on_write() {
+        let mut a = Memory::new(&[1, 2, 3]);
+        let b = a.clone();
+
+        a.set(0, 10);
+        a.set(5000, 1);
+
+        assert_eq!(1, b.get(0));
+        assert_eq!(0, b.get(5000));
+
+        let mut c = a.clone();
+        assert!(Arc::ptr_eq(&a.pages[&4], &c.pages[&4]));
+
+        c.set(5001, 2);
+        assert_eq!(0, a.get(5001));
+        assert_eq!(1, c.get(5000));
+
+        a.reset();
+        assert_eq!(&[1, 2, 3], a.program());
+        assert_eq!(0, a.get(5000));
+        assert!(Arc::ptr_eq(&a.dense, &b.dense));
+    }
+}
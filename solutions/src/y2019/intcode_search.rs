@@ -0,0 +1,151 @@
+#![allow(unused)]
+
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+use crate::y2019::intcode_computer::IntcodeComputer;
+
+/// Shared implementation of the searches: every state keeps the machine that reached
+/// it, which is cloned to try each move from there. `apply` drives the machine with
+/// a move, and returns the new state, or `None` when the move is impossible.
+fn explore<S, M, FM, IM, FA, FS>(
+    start: S,
+    machine: IntcodeComputer,
+    mut moves: FM,
+    mut apply: FA,
+    mut success: FS,
+) -> (Option<(S, IntcodeComputer)>, HashMap<S, usize>)
+where
+    S: Clone + Eq + Hash,
+    FM: FnMut(&S) -> IM,
+    IM: IntoIterator<Item = M>,
+    FA: FnMut(&S, &M, &mut IntcodeComputer) -> Option<S>,
+    FS: FnMut(&S) -> bool,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    distances.insert(start.clone(), 0);
+    queue.push_back((start, machine));
+
+    while let Some((state, machine)) = queue.pop_front() {
+        if success(&state) {
+            return (Some((state, machine)), distances);
+        }
+
+        let distance = distances[&state];
+
+        for m in moves(&state) {
+            let mut next_machine = machine.clone();
+
+            if let Some(next) = apply(&state, &m, &mut next_machine) {
+                if !distances.contains_key(&next) {
+                    distances.insert(next.clone(), distance + 1);
+                    queue.push_back((next, next_machine));
+                }
+            }
+        }
+    }
+
+    (None, distances)
+}
+
+/// First state matching `success` that the machine can be driven to, along with its
+/// number of moves and the machine in that state.
+pub fn bfs<S, M, FM, IM, FA, FS>(
+    start: S,
+    machine: IntcodeComputer,
+    moves: FM,
+    apply: FA,
+    success: FS,
+) -> Option<(S, usize, IntcodeComputer)>
+where
+    S: Clone + Eq + Hash,
+    FM: FnMut(&S) -> IM,
+    IM: IntoIterator<Item = M>,
+    FA: FnMut(&S, &M, &mut IntcodeComputer) -> Option<S>,
+    FS: FnMut(&S) -> bool,
+{
+    let (found, distances) = explore(start, machine, moves, apply, success);
+
+    found.map(|(state, machine)| {
+        let distance = distances[&state];
+        (state, distance, machine)
+    })
+}
+
+/// Number of moves needed to reach every state reachable from `start`.
+pub fn bfs_distances<S, M, FM, IM, FA>(
+    start: S,
+    machine: IntcodeComputer,
+    moves: FM,
+    apply: FA,
+) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    FM: FnMut(&S) -> IM,
+    IM: IntoIterator<Item = M>,
+    FA: FnMut(&S, &M, &mut IntcodeComputer) -> Option<S>,
+{
+    explore(start, machine, moves, apply, |_| false).1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2019::{intcode_assembly::assemble, intcode_computer::State};
+
+    /// A corridor from 0 to 3: 1 moves left, 2 moves right. Outputs 0 when hitting a
+    /// wall, 2 when reaching the end, 1 otherwise.
+    const CORRIDOR: &str = "
+        loop:   IN @dir
+                EQ @dir, #2, @tmp
+                MUL @tmp, #2, @tmp
+                ADD @tmp, @pos, @new
+                ADD @new, #-1, @new
+                LT @new, #0, @tmp
+                JT @tmp, #wall
+                LT #3, @new, @tmp
+                JT @tmp, #wall
+                ADD @new, #0, @pos
+                EQ @pos, #3, @tmp
+                ADD @tmp, #1, @tmp
+                OUT @tmp
+                JT #1, #loop
+        wall:   OUT #0
+                JT #1, #loop
+        dir:    DATA 0
+        tmp:    DATA 0
+        pos:    DATA 0
+        new:    DATA 0
+    ";
+
+    fn walk(pos: &i64, dir: &i64, machine: &mut IntcodeComputer) -> Option<i64> {
+        machine.provide_input(vec![*dir]);
+
+        match machine.process() {
+            State::GaveOutput(0) => None,
+            State::GaveOutput(_) => Some(if *dir == 2 { pos + 1 } else { pos - 1 }),
+            s => panic!("Unexpected state: {:?}", s),
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let machine = IntcodeComputer::from(&assemble(CORRIDOR).unwrap(), vec![]);
+
+        let (end, distance, mut end_machine) =
+            bfs(0, machine.clone(), |_| vec![1, 2], walk, |&p| p == 3).unwrap();
+        assert_eq!((3, 3), (end, distance));
+
+        // The machine is left at the end of the corridor.
+        assert_eq!(None, walk(&3, &2, &mut end_machine));
+        assert_eq!(Some(2), walk(&3, &1, &mut end_machine));
+
+        let distances = bfs_distances(0, machine, |_| vec![1, 2], walk);
+        assert_eq!(4, distances.len());
+        assert_eq!(Some(&3), distances.get(&3));
+    }
+}
@@ -1,4 +1,10 @@
 pub mod helpers;
+pub mod intcode_ascii;
+pub mod intcode_assembly;
+pub mod intcode_cluster;
 pub mod intcode_computer;
+pub mod intcode_debugger;
+pub mod intcode_memory;
+pub mod intcode_search;
 
 crate::days_gen!();
@@ -1,38 +1,68 @@
+use crate::utils::math::crt;
+
 pub struct Day13;
 
-crate::impl_day!("13", false);
+crate::impl_day!("13", true);
+
+/// The earliest departure, and the buses with their position in the list.
+fn process_input(input: &str) -> (i64, Vec<(i64, i64)>) {
+    let mut lines = input.trim().lines();
+
+    let earliest = lines.next().unwrap().parse().unwrap();
+    let buses = lines
+        .next()
+        .unwrap()
+        .split(',')
+        .enumerate()
+        .filter_map(|(i, id)| id.parse().ok().map(|id| (i as i64, id)))
+        .collect();
 
-fn process_input(input: &str) -> &str {
-    input
+    (earliest, buses)
 }
 
-#[allow(unused)]
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    let (earliest, buses) = process_input(input);
 
-    Box::new("Part 1 not done")
+    let (wait, id) = buses
+        .iter()
+        .map(|&(_, id)| ((id - earliest % id) % id, id))
+        .min()
+        .unwrap();
+
+    Box::new(wait * id)
 }
 
-#[allow(unused)]
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    let (_, buses) = process_input(input);
+
+    // Bus `id` at position `i` leaves at `t + i`, i.e. `t = -i (mod id)`.
+    let congruences = buses
+        .iter()
+        .map(|&(i, id)| (-i128::from(i), i128::from(id)))
+        .collect::<Vec<_>>();
+    let (res, _) = crt(&congruences).unwrap();
 
-    Box::new("Part 2 not done")
+    Box::new(res)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = r"";
+    const INPUT: &str = r"939
+7,13,x,x,59,x,31,19";
 
     #[test]
     fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
+        assert_eq!(295.to_string(), *solve_part1(INPUT).to_string());
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
+        assert_eq!(1_068_781.to_string(), *solve_part2(INPUT).to_string());
+        assert_eq!(
+            1_202_161_486.to_string(),
+            *solve_part2("0\n1789,37,47,1889").to_string()
+        );
     }
 }
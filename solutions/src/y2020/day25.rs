@@ -1,22 +1,29 @@
+use itertools::Itertools;
+
+use crate::utils::math::{discrete_log, mod_pow};
+
 pub struct Day25;
 
-crate::impl_day!("25", false);
+crate::impl_day!("25", true);
+
+const MODULUS: i64 = 20_201_227;
+const SUBJECT: i64 = 7;
 
-fn process_input(input: &str) -> &str {
-    input
+fn process_input(input: &str) -> (i64, i64) {
+    input.lines().flat_map(str::parse).collect_tuple().unwrap()
 }
 
-#[allow(unused)]
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    let (card_key, door_key) = process_input(input);
+
+    let card_loop_size = discrete_log(SUBJECT, card_key, MODULUS).unwrap();
+    let res = mod_pow(door_key, card_loop_size, MODULUS);
 
-    Box::new("Part 1 not done")
+    Box::new(res)
 }
 
 #[allow(unused)]
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
-
     Box::new("Part 2 not done")
 }
 
@@ -24,15 +31,11 @@ fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
 mod tests {
     use super::*;
 
-    const INPUT: &str = r"";
+    const INPUT: &str = r"5764801
+17807724";
 
     #[test]
     fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
+        assert_eq!(14_897_079.to_string(), *solve_part1(INPUT).to_string());
     }
 }
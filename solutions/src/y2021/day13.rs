@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use crate::utils::ocr::letters_or_picture;
+
 pub struct Day13;
 
 crate::impl_day!("13", true);
@@ -80,13 +82,8 @@ fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
     let mut max_y = 0;
 
     for &(x, y) in &set {
-        if x > max_x {
-            max_x = x + 1;
-        }
-
-        if y > max_y {
-            max_y = y + 1;
-        }
+        max_x = max_x.max(x + 1);
+        max_y = max_y.max(y + 1);
     }
 
     let mut v = vec![format!("{}", " ".repeat(max_x as usize)); max_y as usize];
@@ -95,7 +92,10 @@ fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
         v[y as usize].replace_range((x as usize)..=(x as usize), "X");
     }
 
-    Box::new(format!("\n{}", v.join("\n").replace('X', "█")))
+    Box::new(letters_or_picture(format!(
+        "\n{}",
+        v.join("\n").replace('X', "█")
+    )))
 }
 
 #[cfg(test)]
@@ -133,6 +133,10 @@ fold along x=5
 
     #[test]
     fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
+        // The example draws a square, which isn't a letter.
+        assert_eq!(
+            "\n█████\n█   █\n█   █\n█   █\n█████",
+            solve_part2(INPUT).to_string()
+        );
     }
 }
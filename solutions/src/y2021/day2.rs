@@ -1,73 +1,43 @@
-use pest_consume::{match_nodes, Error, Parser};
-type Result<T> = std::result::Result<T, Error<Rule>>;
-type Node<'i> = pest_consume::Node<'i, Rule, ()>;
+use crate::utils::parse::{parse_lines, Line, ParseError};
 
 pub struct Day2;
 
 crate::impl_day!("2", true);
 
+#[derive(Debug)]
 enum Direction {
     Forward,
     Down,
     Up,
 }
 
+#[derive(Debug)]
 struct Instruction {
     direction: Direction,
     value: i32,
 }
 
-#[derive(Parser)]
-#[grammar = "y2021/grammars/day2.pest"]
-struct InstructionsParser;
-
-#[allow(clippy::unnecessary_wraps)]
-#[pest_consume::parser]
-impl InstructionsParser {
-    fn EOI(input: Node) -> Result<()> {
-        Ok(())
-    }
-
-    fn direction(input: Node) -> Result<Direction> {
+impl Instruction {
+    fn parse(line: Line<'_>) -> Result<Self, ParseError> {
         use Direction::{Down, Forward, Up};
 
-        match input.as_str() {
-            "forward" => Ok(Forward),
-            "down" => Ok(Down),
-            "up" => Ok(Up),
-            _ => panic!(),
-        }
-    }
-
-    fn value(input: Node) -> Result<i32> {
-        input.as_str().parse::<i32>().map_err(|e| input.error(e))
-    }
-
-    #[allow(clippy::ignored_unit_patterns)]
-    fn instruction(input: Node) -> Result<Instruction> {
-        Ok(match_nodes!(input.into_children();
-            [direction(d), value(v)] => Instruction { direction: d, value: v },
-        ))
+        let (direction, value) = line.split_once(" ")?;
+        let direction = match direction {
+            "forward" => Forward,
+            "down" => Down,
+            "up" => Up,
+            _ => return Err(line.error_at(direction, format!("unknown direction `{}`", direction))),
+        };
+
+        Ok(Self {
+            direction,
+            value: line.parse_part(value)?,
+        })
     }
-
-    #[allow(clippy::ignored_unit_patterns)]
-    fn instructions_set(input: Node) -> Result<Vec<Instruction>> {
-        Ok(match_nodes!(input.into_children();
-            [instruction(i).., EOI(())] => i.collect(),
-        ))
-    }
-}
-
-#[allow(clippy::result_large_err)]
-fn parse_input(input: &str) -> Result<Vec<Instruction>> {
-    let inputs = InstructionsParser::parse(Rule::instructions_set, input).unwrap();
-    let input = inputs.single().unwrap();
-
-    InstructionsParser::instructions_set(input)
 }
 
 fn process_input(input: &str) -> Vec<Instruction> {
-    parse_input(input).unwrap()
+    parse_lines(input, Instruction::parse).unwrap_or_else(|e| panic!("{}", e))
 }
 
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
@@ -125,4 +95,23 @@ forward 2
     fn test_part2() {
         assert_eq!(900.to_string(), *solve_part2(INPUT).to_string());
     }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = |input| {
+            parse_lines(input, Instruction::parse)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            "line 2, column 1: unknown direction `backward`",
+            err("forward 5\nbackward 2")
+        );
+        assert_eq!(
+            "line 1, column 4: can't parse `x`: invalid digit found in string",
+            err("up x")
+        );
+        assert_eq!("line 1, column 5: expected ` `", err("down"));
+    }
 }
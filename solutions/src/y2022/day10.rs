@@ -4,6 +4,8 @@ use anyhow::anyhow;
 use itertools::Itertools;
 use num::abs;
 
+use crate::utils::ocr::letters_or_picture;
+
 pub struct Day10;
 
 crate::impl_day!("10", true);
@@ -164,7 +166,7 @@ fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
         s.push('\n');
     }
 
-    Box::new(s)
+    Box::new(letters_or_picture(s))
 }
 
 #[cfg(test)]
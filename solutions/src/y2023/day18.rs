@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-use crate::utils::direction::Direction;
+use crate::utils::{direction::Direction, geometry::Polygon};
 
 pub struct Day18;
 
@@ -23,35 +23,14 @@ fn process_input(input: &str) -> Vec<Instruction> {
 }
 
 fn solve(instructions: &[Instruction]) -> usize {
-    // Use Gauss's area formula to compute total area based
-    // on polygon coordinates + perimeter length
-
-    let mut pos: (isize, isize) = (0, 0);
-    let mut corners = vec![];
-    let mut trench_length = 0;
-    corners.push(pos);
-
-    for inst in instructions {
-        let Instruction(dir, length, ..) = *inst;
-        let dir_offset: (isize, isize) = dir.into();
-        trench_length += length;
-
-        pos = (
-            pos.0 + dir_offset.0 * length as isize,
-            pos.1 + dir_offset.1 * length as isize,
-        );
-        corners.push(pos);
-    }
-
-    corners
-        .iter()
-        .tuple_windows()
-        .map(|((x0, y0), (x1, y1))| x0 * y1 - x1 * y0)
-        .sum::<isize>()
-        .unsigned_abs()
-        / 2
-        + trench_length / 2
-        + 1
+    // Pick's theorem gives the number of interior points from the area and the
+    // number of points along the trench.
+    Polygon::<i64>::from_instructions(
+        instructions
+            .iter()
+            .map(|Instruction(dir, length, ..)| (*dir, *length as i64)),
+    )
+    .lattice_points() as usize
 }
 
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
@@ -3,6 +3,8 @@ use std::{borrow::Cow, collections::HashMap, sync::OnceLock};
 use itertools::Itertools;
 use regex::Regex;
 
+use crate::utils::ranges::{HyperBox, Interval};
+
 pub struct Day19;
 
 crate::impl_day!("19", true);
@@ -110,11 +112,15 @@ fn get_points(h: &HashMap<String, Vec<String>>, part: &Part, is_part_1: bool) ->
     }
 }
 
-fn solve<I: Iterator<Item = Part>>(workflows: &[Workflow], parts: I) -> usize {
-    let h = workflows
+fn workflows_map(workflows: &[Workflow]) -> HashMap<String, Vec<String>> {
+    workflows
         .iter()
         .map(|v| (v.0.clone(), v.1.clone()))
-        .collect::<HashMap<String, Vec<String>>>();
+        .collect()
+}
+
+fn solve<I: Iterator<Item = Part>>(workflows: &[Workflow], parts: I) -> usize {
+    let h = workflows_map(workflows);
 
     parts.map(|p| get_points(&h, &p, true)).sum()
 }
@@ -126,10 +132,58 @@ fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
     Box::new(score)
 }
 
+/// Ratings of x, m, a and s.
+type Ratings = HyperBox<usize, 4>;
+
+fn count_accepted(h: &HashMap<String, Vec<String>>, workflow: &str, ratings: Ratings) -> usize {
+    match workflow {
+        "A" => return ratings.volume(),
+        "R" => return 0,
+        _ => {}
+    }
+
+    let mut res = 0;
+    let mut remaining = Some(ratings);
+
+    for subflow in &h[workflow] {
+        let current = match remaining {
+            Some(current) => current,
+            None => break,
+        };
+
+        if let Ok((criteria, operation, value, destination)) =
+            sscanf::sscanf!(subflow, "{char}{char}{usize}:{String}")
+        {
+            let axis = "xmas".find(criteria).expect("unknown criteria");
+            let (matching, rest) = match operation {
+                '<' => current.split_at(axis, value),
+                '>' => {
+                    let (lower, upper) = current.split_at(axis, value + 1);
+                    (upper, lower)
+                }
+                _ => unreachable!(),
+            };
+
+            if let Some(matching) = matching {
+                res += count_accepted(h, &destination, matching);
+            }
+            remaining = rest;
+        } else {
+            res += count_accepted(h, subflow, current);
+            remaining = None;
+        }
+    }
+
+    res
+}
+
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
-    let (_, _) = process_input(input);
+    let (workflows, _) = process_input(input);
+    let h = workflows_map(&workflows);
+
+    let res = count_accepted(&h, "in", HyperBox([Interval::inclusive(1, 4000); 4]));
 
-    Box::new(0)
+    Box::new(res)
 }
 
 #[cfg(test)]
@@ -2,6 +2,8 @@ use std::collections::{HashMap, VecDeque};
 
 use itertools::Itertools;
 
+use crate::utils::parse::parse_lines;
+
 pub struct Day20;
 
 crate::impl_day!("20", true);
@@ -13,71 +15,44 @@ enum Module {
     Conjunction(Vec<String>, Vec<(String, bool)>),
 }
 
-fn collect_destination_modules(s: &str) -> Vec<String> {
-    s.split(" -> ")
-        .skip(1)
-        .flat_map(|list| {
-            list.split(", ")
-                .map(std::borrow::ToOwned::to_owned)
-                .collect_vec()
-        })
-        .collect_vec()
-}
-
-fn collect_module_name(s: &str) -> String {
-    if s.starts_with("broadcaster") {
-        "broadcaster".to_string()
-    } else {
-        let first_space_index = s.find(' ').expect("first space not found");
+fn process_input(input: &str) -> HashMap<String, Module> {
+    let modules = parse_lines(input, |line| {
+        let (name, destinations) = line.edges()?;
+        let destinations = destinations
+            .into_iter()
+            .map(std::borrow::ToOwned::to_owned)
+            .collect_vec();
+
+        if name == "broadcaster" {
+            Ok((name.to_owned(), Module::Broadcaster(destinations)))
+        } else if let Some(name) = name.strip_prefix('%') {
+            Ok((name.to_owned(), Module::FlipFlop(destinations, false)))
+        } else if let Some(name) = name.strip_prefix('&') {
+            Ok((name.to_owned(), Module::Conjunction(destinations, vec![])))
+        } else {
+            Err(line.error_at(name, format!("unknown module `{}`", name)))
+        }
+    })
+    .unwrap_or_else(|e| panic!("{}", e));
 
-        s[1..first_space_index].to_owned()
-    }
-}
+    let links = modules
+        .iter()
+        .flat_map(|(name, module)| {
+            let (Module::Broadcaster(destinations)
+            | Module::FlipFlop(destinations, _)
+            | Module::Conjunction(destinations, _)) = module;
 
-fn process_input(input: &str) -> HashMap<String, Module> {
-    let mut conjunction_module_names = vec![];
-
-    let mut h: HashMap<String, Module> = input
-        .trim_end()
-        .lines()
-        .map(|l| {
-            let destination_modules = collect_destination_modules(l);
-            let module_name = collect_module_name(l);
-
-            if module_name.as_str() == "broadcaster" {
-                (module_name, Module::Broadcaster(destination_modules))
-            } else {
-                let is_flip_flop = l.starts_with('%');
-
-                (
-                    module_name.clone(),
-                    if is_flip_flop {
-                        Module::FlipFlop(destination_modules, false)
-                    } else {
-                        conjunction_module_names.push(module_name);
-
-                        Module::Conjunction(destination_modules, vec![])
-                    },
-                )
-            }
+            destinations.iter().map(move |d| (name.clone(), d.clone()))
         })
-        .collect();
+        .collect_vec();
 
-    input.lines().for_each(|l| {
-        let destination_modules = collect_destination_modules(l);
-        let module_name = collect_module_name(l);
+    let mut h: HashMap<String, Module> = modules.into_iter().collect();
 
-        for dest in destination_modules {
-            if conjunction_module_names.contains(&dest) {
-                h.entry(dest).and_modify(|v| match v {
-                    Module::Conjunction(_, b) => {
-                        b.push((module_name.clone(), false));
-                    }
-                    _ => panic!("not a conjunction module"),
-                });
-            }
+    for (source, destination) in links {
+        if let Some(Module::Conjunction(_, inputs)) = h.get_mut(&destination) {
+            inputs.push((source, false));
         }
-    });
+    }
 
     h
 }
@@ -1,24 +1,94 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::utils::sequence::interpolate;
+
 pub struct Day21;
 
-crate::impl_day!("21", false);
+crate::impl_day!("21", true);
+
+/// The rocks, and the starting position.
+fn process_input(input: &str) -> (Vec<Vec<bool>>, (i64, i64)) {
+    let mut start = (0, 0);
+
+    let rocks = input
+        .trim()
+        .lines()
+        .enumerate()
+        .map(|(y, l)| {
+            l.chars()
+                .enumerate()
+                .map(|(x, c)| {
+                    if c == 'S' {
+                        start = (x as i64, y as i64);
+                    }
+
+                    c == '#'
+                })
+                .collect()
+        })
+        .collect();
+
+    (rocks, start)
+}
+
+/// Number of plots reachable in exactly `steps` steps, on the garden repeated
+/// infinitely in every direction. A plot reachable in fewer steps is reachable again
+/// two steps later, by going back and forth.
+fn reachable_plots(rocks: &[Vec<bool>], start: (i64, i64), steps: usize) -> usize {
+    let (width, height) = (rocks[0].len() as i64, rocks.len() as i64);
+    let is_rock =
+        |(x, y): (i64, i64)| rocks[y.rem_euclid(height) as usize][x.rem_euclid(width) as usize];
+
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+    let mut res = 0;
+
+    seen.insert(start);
+    queue.push_back((start, 0));
 
-fn process_input(input: &str) -> &str {
-    input
+    while let Some(((x, y), distance)) = queue.pop_front() {
+        if distance % 2 == steps % 2 {
+            res += 1;
+        }
+
+        if distance == steps {
+            continue;
+        }
+
+        for next in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+            if !is_rock(next) && seen.insert(next) {
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    res
 }
 
-#[allow(unused)]
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    let (rocks, start) = process_input(input);
 
-    let res = "Part 1 not done";
-    Box::new(res)
+    Box::new(reachable_plots(&rocks, start, 64))
 }
 
-#[allow(unused)]
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    const STEPS: usize = 26_501_365;
+
+    let (rocks, start) = process_input(input);
+    let size = rocks.len();
+
+    // The start row and column are free of rocks, so every `size` steps the reachable
+    // area grows by a copy of the garden in each direction: the number of plots is a
+    // quadratic function of the number of copies crossed.
+    let points = (0..3)
+        .map(|n| {
+            let steps = STEPS % size + n * size;
+            (n as i128, reachable_plots(&rocks, start, steps) as i128)
+        })
+        .collect::<Vec<_>>();
+
+    let res = interpolate(&points, (STEPS / size) as i128).to_integer();
 
-    let res = "Part 2 not done";
     Box::new(res)
 }
 
@@ -26,15 +96,25 @@ fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
 mod tests {
     use super::*;
 
-    const INPUT: &str = r"";
+    const INPUT: &str = r"...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
 
     #[test]
-    fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
-    }
+    fn test_reachable_plots() {
+        let (rocks, start) = process_input(INPUT);
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
+        assert_eq!(16, reachable_plots(&rocks, start, 6));
+        assert_eq!(50, reachable_plots(&rocks, start, 10));
+        assert_eq!(1594, reachable_plots(&rocks, start, 50));
+        assert_eq!(6536, reachable_plots(&rocks, start, 100));
     }
 }
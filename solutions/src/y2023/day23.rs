@@ -1,24 +1,79 @@
+use itertools::Itertools;
+use petgraph::graphmap::DiGraphMap;
+
+use crate::utils::{
+    graph::{compress_corridors, dag_longest_path, longest_simple_path},
+    grid::Grid,
+};
+
 pub struct Day23;
 
-crate::impl_day!("23", false);
+crate::impl_day!("23", true);
+
+type Pos = (usize, usize);
+
+fn process_input(input: &str) -> Grid<char> {
+    let mut grid = Grid::new();
+
+    for l in input.lines().filter(|l| !l.is_empty()) {
+        grid.push_row(l.chars().collect_vec());
+    }
+
+    grid
+}
+
+/// The open tiles of the top and bottom rows.
+fn start_and_end(grid: &Grid<char>) -> (Pos, Pos) {
+    let open = |y: usize| {
+        grid.iter_row(y)
+            .position(|&c| c == '.')
+            .map(|x| (x, y))
+            .unwrap()
+    };
+
+    (open(0), open(grid.rows() - 1))
+}
+
+/// Hikes with `slippery` slopes can only go down them.
+fn trails(grid: &Grid<char>, slippery: bool) -> (DiGraphMap<Pos, usize>, Pos, Pos) {
+    let (start, end) = start_and_end(grid);
+
+    let successors = |&(x, y): &Pos| {
+        let downhill = match grid.get(x, y) {
+            Some('>') => Some((x + 1, y)),
+            Some('<') => Some((x - 1, y)),
+            Some('v') => Some((x, y + 1)),
+            Some('^') => Some((x, y - 1)),
+            _ => None,
+        };
+
+        match downhill {
+            Some(next) if slippery => vec![next],
+            _ => grid
+                .ortho_neighbour_positions(x, y)
+                .filter(|&(nx, ny)| grid.get(nx, ny) != Some(&'#'))
+                .collect_vec(),
+        }
+    };
 
-fn process_input(input: &str) -> &str {
-    input
+    (compress_corridors(&[start, end], successors), start, end)
 }
 
-#[allow(unused)]
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    let grid = process_input(input);
+    let (trails, start, end) = trails(&grid, true);
 
-    let res = "Part 1 not done";
+    let (res, _) = dag_longest_path(&trails, start, end)
+        .unwrap_or_else(|e| panic!("The slopes should prevent loops: {}", e))
+        .unwrap();
     Box::new(res)
 }
 
-#[allow(unused)]
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    let grid = process_input(input);
+    let (trails, start, end) = trails(&grid, false);
 
-    let res = "Part 2 not done";
+    let res = longest_simple_path(&trails, start, end).unwrap();
     Box::new(res)
 }
 
@@ -26,15 +81,37 @@ fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
 mod tests {
     use super::*;
 
-    const INPUT: &str = r"";
+    const INPUT: &str = r"#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#";
 
     #[test]
     fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
+        assert_eq!(94.to_string(), *solve_part1(INPUT).to_string());
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
+        assert_eq!(154.to_string(), *solve_part2(INPUT).to_string());
     }
 }
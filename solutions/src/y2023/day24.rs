@@ -1,24 +1,137 @@
+use std::convert::TryInto;
+
+use itertools::Itertools;
+use num::{rational::Ratio, BigInt};
+
+use crate::utils::matrix::Matrix;
+
 pub struct Day24;
 
-crate::impl_day!("24", false);
+crate::impl_day!("24", true);
+
+type Vec3 = [i64; 3];
+
+#[derive(Debug)]
+struct Hailstone {
+    position: Vec3,
+    velocity: Vec3,
+}
+
+fn process_input(input: &str) -> Vec<Hailstone> {
+    let parse = |s: &str| -> Vec3 {
+        s.split(',')
+            .map(|v| v.trim().parse().unwrap())
+            .collect_vec()
+            .try_into()
+            .unwrap()
+    };
 
-fn process_input(input: &str) -> &str {
     input
+        .trim()
+        .lines()
+        .map(|l| {
+            let (position, velocity) = l.split_once('@').unwrap();
+
+            Hailstone {
+                position: parse(position),
+                velocity: parse(velocity),
+            }
+        })
+        .collect()
+}
+
+/// Whether the paths of two hailstones cross inside the test area, ignoring the Z axis.
+fn paths_cross(a: &Hailstone, b: &Hailstone, min: i64, max: i64) -> bool {
+    let r = |v: i64| Ratio::from_integer(i128::from(v));
+
+    // a.position + t * a.velocity = b.position + s * b.velocity
+    let m = Matrix::from(vec![
+        vec![r(a.velocity[0]), -r(b.velocity[0])],
+        vec![r(a.velocity[1]), -r(b.velocity[1])],
+    ]);
+    let rhs = [
+        r(b.position[0] - a.position[0]),
+        r(b.position[1] - a.position[1]),
+    ];
+
+    let (t, s) = match m.solve(&rhs) {
+        Some(v) => (v[0], v[1]),
+        None => return false,
+    };
+
+    let area = r(min)..=r(max);
+    let x = r(a.position[0]) + t * r(a.velocity[0]);
+    let y = r(a.position[1]) + t * r(a.velocity[1]);
+
+    t >= r(0) && s >= r(0) && area.contains(&x) && area.contains(&y)
+}
+
+fn count_crossings(hailstones: &[Hailstone], min: i64, max: i64) -> usize {
+    hailstones
+        .iter()
+        .tuple_combinations()
+        .filter(|(a, b)| paths_cross(a, b, min, max))
+        .count()
 }
 
-#[allow(unused)]
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    let hailstones = process_input(input);
+
+    let res = count_crossings(&hailstones, 200_000_000_000_000, 400_000_000_000_000);
 
-    let res = "Part 1 not done";
     Box::new(res)
 }
 
-#[allow(unused)]
+/// Matrix of `x -> x × v`.
+fn cross_left(v: Vec3) -> [Vec3; 3] {
+    [[0, v[2], -v[1]], [-v[2], 0, v[0]], [v[1], -v[0], 0]]
+}
+
+fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    let hailstones = process_input(input);
+
+    // The rock (p, v) hits every hailstone (p_i, v_i), so (p - p_i) × (v - v_i) = 0.
+    // The only non-linear term is p × v, which is removed by subtracting the equation
+    // of another hailstone:
+    // p × (v_i - v_j) + (p_i - p_j) × v = p_i × v_i - p_j × v_j
+    let mut rows = vec![];
+    let mut rhs = vec![];
+
+    for j in 1..=2 {
+        let (a, b) = (&hailstones[0], &hailstones[j]);
+
+        let p_coefficients = cross_left(sub(a.velocity, b.velocity));
+        // d × v = -(v × d)
+        let v_coefficients = cross_left(sub(a.position, b.position)).map(|r| r.map(|c| -c));
+        let constant = sub(cross(a.position, a.velocity), cross(b.position, b.velocity));
+
+        for axis in 0..3 {
+            rows.push(
+                p_coefficients[axis]
+                    .iter()
+                    .chain(&v_coefficients[axis])
+                    .map(|&c| BigInt::from(c))
+                    .collect_vec(),
+            );
+            rhs.push(BigInt::from(constant[axis]));
+        }
+    }
+
+    let rock = Matrix::from(rows).solve_integer(&rhs).unwrap();
+    let res: BigInt = rock[..3].iter().sum();
 
-    let res = "Part 2 not done";
     Box::new(res)
 }
 
@@ -26,15 +139,19 @@ fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
 mod tests {
     use super::*;
 
-    const INPUT: &str = r"";
+    const INPUT: &str = r"19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
 
     #[test]
     fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
+        assert_eq!(2, count_crossings(&process_input(INPUT), 7, 27));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
+        assert_eq!(47.to_string(), *solve_part2(INPUT).to_string());
     }
 }
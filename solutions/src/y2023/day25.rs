@@ -1,40 +1,44 @@
-pub struct Day25;
+use crate::utils::graph::{min_cut, parse_ungraph};
 
-crate::impl_day!("25", false);
+pub struct Day25;
 
-fn process_input(input: &str) -> &str {
-    input
-}
+crate::impl_day!("25", true);
 
-#[allow(unused)]
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    let graph = parse_ungraph(input);
+
+    let (cut, group) = min_cut(&graph).unwrap();
+    assert_eq!(3, cut, "The components can't be split by cutting 3 wires");
 
-    let res = "Part 1 not done";
+    let res = group.len() * (graph.node_count() - group.len());
     Box::new(res)
 }
 
 #[allow(unused)]
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
-
-    let res = "Part 2 not done";
-    Box::new(res)
+    Box::new("Part 2 not done")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = r"";
+    const INPUT: &str = r"jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
 
     #[test]
     fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
+        assert_eq!(54.to_string(), *solve_part1(INPUT).to_string());
     }
 }
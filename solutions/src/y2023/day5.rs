@@ -1,5 +1,10 @@
 use itertools::Itertools;
 
+use crate::utils::{
+    parse::{ints, ints_n},
+    ranges::{Interval, IntervalSet},
+};
+
 pub struct Day5;
 
 crate::impl_day!("5", true);
@@ -42,7 +47,7 @@ fn process_input(input: &str) -> Rules {
 
     for l in input.trim_end().lines().filter(|s| !s.is_empty()) {
         if l.starts_with("seeds: ") {
-            seeds = l.split(' ').skip(1).flat_map(str::parse).collect_vec();
+            seeds = ints(l).collect_vec();
 
             continue;
         }
@@ -56,12 +61,8 @@ fn process_input(input: &str) -> Rules {
             continue;
         }
 
-        temp_map.push(
-            l.split(' ')
-                .flat_map(str::parse::<i64>)
-                .collect_tuple()
-                .unwrap(),
-        );
+        let [dest_range, source_range, range_length] = ints_n(l);
+        temp_map.push((dest_range, source_range, range_length));
     }
 
     if !temp_map.is_empty() {
@@ -86,19 +87,29 @@ fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
 
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
     let input = process_input(input);
-    let mut min = i64::MAX;
-
-    for mut chunk in &input.seeds.iter().chunks(2) {
-        let &start = chunk.next().unwrap();
-        let &length = chunk.next().unwrap();
-        let end = start + length;
 
-        for seed in start..end {
-            min = min.min(input.compute_final_location(seed));
-        }
-    }
-
-    Box::new(min)
+    let seeds: IntervalSet<i64> = input
+        .seeds
+        .iter()
+        .tuples()
+        .map(|(&start, &length)| Interval::with_len(start, length))
+        .collect();
+
+    let locations = input.maps.iter().fold(seeds, |acc, map| {
+        let rules = map
+            .iter()
+            .map(|&(dest_range, source_range, range_length)| {
+                (
+                    Interval::with_len(source_range, range_length),
+                    dest_range - source_range,
+                )
+            })
+            .collect_vec();
+
+        acc.map_offsets(&rules)
+    });
+
+    Box::new(locations.min().unwrap())
 }
 
 #[cfg(test)]
@@ -1,53 +1,24 @@
-use std::borrow::Cow;
-
 use itertools::Itertools;
 
+use crate::utils::{
+    parse::line_ints,
+    sequence::{extrapolate_next, extrapolate_previous},
+};
+
 pub struct Day9;
 
 crate::impl_day!("9", true);
 
 fn process_input(input: &str) -> Vec<Vec<i32>> {
-    input
-        .trim_end()
-        .lines()
-        .map(|l| l.split_whitespace().flat_map(str::parse).collect_vec())
+    line_ints(input.trim_end())
+        .map(Iterator::collect)
         .collect_vec()
 }
 
-fn compute_value(input: &[i32], previous: bool) -> i32 {
-    let mut sequences = vec![];
-
-    sequences.push(input.to_owned());
-    let mut last_sequence = Cow::Borrowed(input);
-
-    loop {
-        let new_sequence = last_sequence
-            .iter()
-            .tuple_windows()
-            .map(|(a, b)| b - a)
-            .collect_vec();
-
-        sequences.push(new_sequence.clone());
-        last_sequence = Cow::Owned(new_sequence);
-
-        if last_sequence.iter().all(num::Zero::is_zero) {
-            break;
-        }
-    }
-
-    sequences.iter().rev().fold(0, |acc, s| {
-        if previous {
-            s.first().expect("no first element") - acc
-        } else {
-            acc + s.last().expect("no last element")
-        }
-    })
-}
-
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
     let input = process_input(input);
 
-    let res = input.iter().fold(0, |acc, v| acc + compute_value(v, false));
+    let res = input.iter().fold(0, |acc, v| acc + extrapolate_next(v));
 
     Box::new(res)
 }
@@ -55,7 +26,7 @@ fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
     let input = process_input(input);
 
-    let res = input.iter().fold(0, |acc, v| acc + compute_value(v, true));
+    let res = input.iter().fold(0, |acc, v| acc + extrapolate_previous(v));
 
     Box::new(res)
 }
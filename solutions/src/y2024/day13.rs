@@ -1,24 +1,61 @@
+use crate::utils::matrix::Matrix;
+
 pub struct Day13;
 
-crate::impl_day!("13", false);
+crate::impl_day!("13", true);
+
+struct Machine {
+    a: (i64, i64),
+    b: (i64, i64),
+    prize: (i64, i64),
+}
+
+impl Machine {
+    /// Tokens needed to win the prize, if it can be won.
+    fn cost(&self, offset: i64, max_presses: Option<i64>) -> Option<i64> {
+        let buttons = Matrix::from(vec![vec![self.a.0, self.b.0], vec![self.a.1, self.b.1]]);
+        let prize = [self.prize.0 + offset, self.prize.1 + offset];
+
+        match buttons.solve_integer(&prize)?.as_slice() {
+            &[a, b] if a >= 0 && b >= 0 && max_presses.is_none_or(|m| a <= m && b <= m) => {
+                Some(3 * a + b)
+            }
+            _ => None,
+        }
+    }
+}
 
-fn process_input(input: &str) -> &str {
+fn process_input(input: &str) -> Vec<Machine> {
     input
+        .trim()
+        .split("\n\n")
+        .map(|block| {
+            let mut lines = block.lines();
+            let a = sscanf::sscanf!(lines.next().unwrap(), "Button A: X+{i64}, Y+{i64}").unwrap();
+            let b = sscanf::sscanf!(lines.next().unwrap(), "Button B: X+{i64}, Y+{i64}").unwrap();
+            let prize = sscanf::sscanf!(lines.next().unwrap(), "Prize: X={i64}, Y={i64}").unwrap();
+
+            Machine { a, b, prize }
+        })
+        .collect()
 }
 
-#[allow(unused)]
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    let machines = process_input(input);
+
+    let res: i64 = machines.iter().filter_map(|m| m.cost(0, Some(100))).sum();
 
-    let res = "Part 1 not done";
     Box::new(res)
 }
 
-#[allow(unused)]
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    let machines = process_input(input);
+
+    let res: i64 = machines
+        .iter()
+        .filter_map(|m| m.cost(10_000_000_000_000, None))
+        .sum();
 
-    let res = "Part 2 not done";
     Box::new(res)
 }
 
@@ -26,15 +63,32 @@ fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
 mod tests {
     use super::*;
 
-    const INPUT: &str = r"";
+    const INPUT: &str = r"Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279";
 
     #[test]
     fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
+        assert_eq!(480.to_string(), *solve_part1(INPUT).to_string());
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
+        assert_eq!(
+            875_318_608_908_i64.to_string(),
+            *solve_part2(INPUT).to_string()
+        );
     }
 }
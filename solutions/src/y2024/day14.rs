@@ -1,11 +1,19 @@
+use std::{collections::HashSet, iter::successors};
+
 use itertools::Itertools;
 use regex::Regex;
 
+use crate::utils::{
+    detectors::{largest_component, max_by_score},
+    grid::Grid,
+    image, viz,
+};
+
 pub struct Day14;
 
 crate::impl_day!("14", true);
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct Robot((isize, isize), (isize, isize));
 
 fn process_input(input: &str) -> Vec<Robot> {
@@ -53,39 +61,40 @@ fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
     let width: isize = 101;
     let height: isize = 103;
 
-    solve_with_dims(input, width, height, false)
+    solve_with_dims(input, width, height)
 }
 
-fn display(robots: &[Robot], width: isize, height: isize) {
-    for y in 0..height {
-        for x in 0..width {
-            if robots.iter().any(|Robot(pos, _)| pos.0 == x && pos.1 == y) {
-                print!("#");
-            } else {
-                print!(" ");
-            }
-        }
+fn positions(robots: &[Robot]) -> Vec<(isize, isize)> {
+    robots.iter().map(|Robot(pos, _)| *pos).collect_vec()
+}
+
+/// Lit pixels where there are robots.
+fn picture(robots: &[Robot], width: isize, height: isize) -> Grid<bool> {
+    let positions = positions(robots).into_iter().collect::<HashSet<_>>();
+    let pixels = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|pos| positions.contains(&pos))
+        .collect_vec();
 
-        println!();
+    Grid::from_data(width as usize, pixels)
+}
+
+fn display(step: usize, robots: &[Robot], width: isize, height: isize) {
+    if !viz::enabled() {
+        return;
     }
-    println!("----------------------------");
+
+    viz::show(
+        format!("Step {step}"),
+        &picture(robots, width, height),
+        viz::pixels,
+    );
 }
 
-fn solve_with_dims(
-    input: &str,
-    width: isize,
-    height: isize,
-    display_results: bool,
-) -> Box<dyn std::fmt::Display> {
+fn solve_with_dims(input: &str, width: isize, height: isize) -> Box<dyn std::fmt::Display> {
     let mut robots = process_input(input);
 
-    for i in 0..100 {
-        if display_results && i > 7000 {
-            println!("----------------------------");
-            println!("Step: {i}");
-            display(&robots, width, height);
-        }
-
+    for _ in 0..100 {
         step(&mut robots, width, height);
     }
 
@@ -118,7 +127,33 @@ fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
     let width: isize = 101;
     let height: isize = 103;
 
-    solve_with_dims(input, width, height, true)
+    Box::new(find_tree(input, width, height))
+}
+
+fn find_tree(input: &str, width: isize, height: isize) -> usize {
+    // The robots are back where they started after `width * height` steps.
+    let states = successors(Some(process_input(input)), |robots| {
+        let mut next = robots.clone();
+        step(&mut next, width, height);
+
+        Some(next)
+    })
+    .take((width * height) as usize);
+
+    // The tree is drawn with a frame around it, far bigger than any group of robots
+    // that are next to each other by chance.
+    let (i, robots) = max_by_score(states, |robots| largest_component(&positions(robots))).unwrap();
+    display(i, &robots, width, height);
+
+    if image::export_enabled() {
+        let tree = picture(&robots, width, height);
+
+        if let Err(e) = image::export("2024-day14-tree", &tree, image::pixels) {
+            log::warn!("Can't export the tree: {}", e);
+        }
+    }
+
+    i
 }
 
 #[cfg(test)]
@@ -140,9 +175,22 @@ p=9,5 v=-3,-3";
 
     #[test]
     fn test_part1() {
-        assert_eq!(
-            12.to_string(),
-            *solve_with_dims(INPUT, 11, 7, false).to_string()
-        );
+        assert_eq!(12.to_string(), *solve_with_dims(INPUT, 11, 7).to_string());
+    }
+
+    #[test]
+    fn test_find_tree() {
+        // Robots lined up at step 5 only.
+        let input = (0..5_isize)
+            .map(|i| {
+                format!(
+                    "p={},{} v={i},{i}",
+                    (-4 * i).rem_euclid(11),
+                    (3 - 5 * i).rem_euclid(7)
+                )
+            })
+            .join("\n");
+
+        assert_eq!(5, find_tree(&input, 11, 7));
     }
 }
@@ -1,15 +1,14 @@
 use itertools::Itertools;
 use num::abs;
 
+use crate::utils::parse::line_ints;
+
 pub struct Day2;
 
 crate::impl_day!("2", true);
 
 fn process_input(input: &str) -> Vec<Vec<i32>> {
-    input
-        .lines()
-        .map(|l| l.split(' ').map(|n| n.parse::<i32>().unwrap()).collect())
-        .collect()
+    line_ints(input).map(Iterator::collect).collect()
 }
 
 fn is_safe(report: &[i32]) -> bool {
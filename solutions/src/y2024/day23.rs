@@ -1,24 +1,35 @@
-pub struct Day23;
+use itertools::Itertools;
 
-crate::impl_day!("23", false);
+use crate::utils::graph::{largest_clique, parse_ungraph};
 
-fn process_input(input: &str) -> &str {
-    input
-}
+pub struct Day23;
+
+crate::impl_day!("23", true);
 
-#[allow(unused)]
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    let graph = parse_ungraph(input);
+
+    // Every triangle is counted once, from its lowest computer.
+    let res = graph
+        .nodes()
+        .flat_map(|a| {
+            graph
+                .neighbors(a)
+                .filter(move |&b| a < b)
+                .tuple_combinations()
+                .filter(|&(b, c)| graph.contains_edge(b, c))
+                .map(move |(b, c)| [a, b, c])
+        })
+        .filter(|computers| computers.iter().any(|c| c.starts_with('t')))
+        .count();
 
-    let res = "Part 1 not done";
     Box::new(res)
 }
 
-#[allow(unused)]
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
-    let input = process_input(input);
+    let graph = parse_ungraph(input);
 
-    let res = "Part 2 not done";
+    let res = largest_clique(&graph).into_iter().sorted().join(",");
     Box::new(res)
 }
 
@@ -26,15 +37,46 @@ fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
 mod tests {
     use super::*;
 
-    const INPUT: &str = r"";
+    const INPUT: &str = r"kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn";
 
     #[test]
     fn test_part1() {
-        assert_eq!(0.to_string(), *solve_part1(INPUT).to_string());
+        assert_eq!(7.to_string(), *solve_part1(INPUT).to_string());
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(0.to_string(), *solve_part2(INPUT).to_string());
+        assert_eq!("co,de,ka,ta", solve_part2(INPUT).to_string());
     }
 }
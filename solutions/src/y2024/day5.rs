@@ -1,93 +1,44 @@
-use std::{borrow::Cow, collections::HashMap};
-
 use itertools::Itertools;
+use petgraph::graphmap::DiGraphMap;
+
+use crate::utils::graph::{parse_digraph, toposort_subset};
 
 pub struct Day5;
 
 crate::impl_day!("5", true);
 
-fn process_input(input: &str) -> (String, Vec<Vec<i32>>) {
-    let (first, second) = input
-        .trim()
-        .split("\n\n")
-        .map(std::string::ToString::to_string)
-        .collect_tuple()
-        .unwrap();
+type Rules<'a> = DiGraphMap<&'a str, ()>;
+
+fn process_input(input: &str) -> (Rules<'_>, Vec<Vec<&str>>) {
+    let (rules, updates) = input.trim().split_once("\n\n").unwrap();
 
     (
-        first,
-        second
+        parse_digraph(rules),
+        updates
             .lines()
-            .map(|l| {
-                l.split(',')
-                    .map(|n| n.parse::<i32>().unwrap())
-                    .collect_vec()
-            })
+            .map(|l| l.split(',').collect_vec())
             .collect_vec(),
     )
 }
 
-fn build_dependencies(rules: &str) -> (HashMap<i32, Vec<i32>>, HashMap<i32, Vec<i32>>) {
-    let mut dependencies: HashMap<i32, Vec<i32>> = HashMap::new();
-    let mut reverse_dependencies: HashMap<i32, Vec<i32>> = HashMap::new();
-
-    for rule in rules.lines() {
-        let first = &rule[0..2].parse::<i32>().unwrap();
-        let second = &rule[3..5].parse::<i32>().unwrap();
-
-        dependencies.entry(*second).or_default().push(*first);
-        reverse_dependencies
-            .entry(*first)
-            .or_default()
-            .push(*second);
-    }
-
-    (dependencies, reverse_dependencies)
+fn update_is_valid(rules: &Rules, update: &[&str]) -> bool {
+    update
+        .iter()
+        .tuple_combinations()
+        .all(|(&before, &after)| !rules.contains_edge(after, before))
 }
 
-fn update_is_valid(
-    dependencies: &HashMap<i32, Vec<i32>>,
-    reverse_dependencies: &HashMap<i32, Vec<i32>>,
-    numbers: &[i32],
-) -> bool {
-    let mut encountered_numbers = &numbers[0..0];
-    let mut remaining_numbers = &numbers[1..];
-
-    for (i, number) in numbers.iter().enumerate() {
-        let num_deps = dependencies
-            .get(number)
-            .map_or(Cow::Owned(vec![]), Cow::Borrowed);
-        let num_rev_deps = reverse_dependencies
-            .get(number)
-            .map_or(Cow::Owned(vec![]), Cow::Borrowed);
-
-        if encountered_numbers.iter().any(|n| !num_deps.contains(n)) {
-            return false;
-        }
-        if remaining_numbers.iter().any(|n| !num_rev_deps.contains(n)) {
-            return false;
-        }
-
-        encountered_numbers = &numbers[0..i];
-
-        if i < numbers.len() - 1 {
-            remaining_numbers = &numbers[i + 2..];
-        } else {
-            remaining_numbers = &[];
-        }
-    }
-
-    true
+fn middle_page(update: &[&str]) -> i32 {
+    update[update.len() / 2].parse().unwrap()
 }
 
 fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
     let (rules, updates) = process_input(input);
 
-    let (dependencies, reverse_dependencies) = build_dependencies(&rules);
     let res = updates
         .iter()
-        .filter(|u| update_is_valid(&dependencies, &reverse_dependencies, u))
-        .map(|u| u[u.len() / 2])
+        .filter(|u| update_is_valid(&rules, u))
+        .map(|u| middle_page(u))
         .sum::<i32>();
 
     Box::new(res)
@@ -95,27 +46,17 @@ fn solve_part1(input: &str) -> Box<dyn std::fmt::Display> {
 
 fn solve_part2(input: &str) -> Box<dyn std::fmt::Display> {
     let (rules, updates) = process_input(input);
-    let (dependencies, reverse_dependencies) = build_dependencies(&rules);
-
-    let mut res = 0;
-
-    for update in updates {
-        if update_is_valid(&dependencies, &reverse_dependencies, &update) {
-            continue;
-        }
-
-        // Sort the page numbers
-        let mut sorted_update = update.clone();
-        sorted_update.sort_by_key(|n| {
-            let deps = dependencies
-                .get(n)
-                .map_or(Cow::Owned(vec![]), Cow::Borrowed);
 
-            deps.iter().filter(|n| update.contains(n)).count()
-        });
+    let res = updates
+        .iter()
+        .filter(|u| !update_is_valid(&rules, u))
+        .map(|u| {
+            // The rules between the pages of a single update have no cycle.
+            let sorted = toposort_subset(&rules, u).unwrap_or_else(|e| panic!("{}", e));
 
-        res += sorted_update[sorted_update.len() / 2];
-    }
+            middle_page(&sorted)
+        })
+        .sum::<i32>();
 
     Box::new(res)
 }
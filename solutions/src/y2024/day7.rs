@@ -1,22 +1,14 @@
 use itertools::Itertools;
 
+use crate::utils::parse::line_ints;
+
 pub struct Day7;
 
 crate::impl_day!("7", true);
 
 fn process_input(input: &str) -> Vec<(i64, Vec<i64>)> {
-    input
-        .lines()
-        .filter_map(|l| {
-            l.split(": ").collect_tuple().map(|(a, b)| {
-                (
-                    a.parse::<i64>().unwrap(),
-                    b.split(' ')
-                        .map(|s| s.parse::<i64>().unwrap())
-                        .collect_vec(),
-                )
-            })
-        })
+    line_ints(input)
+        .filter_map(|mut values| values.next().map(|test| (test, values.collect_vec())))
         .collect_vec()
 }
 